@@ -0,0 +1,73 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::broadphase;
+use stad_demos::static_geometry::StaticGeometry;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::Lcg;
+
+
+// n stadiums spread over a square that grows with n, so the number of
+// neighbours each one has stays about the same
+fn scene(n: usize) -> Vec<Stad> {
+    let mut rng = Lcg(n as u64);
+    let side = (n as f64).sqrt() * 60.0;
+    (0..n).map(|_| {
+	let p1 = Point { x: rng.next() * side, y: rng.next() * side };
+	let d = Vector { x: rng.next() * 60.0 - 30.0, y: rng.next() * 60.0 - 30.0 };
+	Stad { p1, p2: p1.plus(d), r: 2.0 + rng.next() * 8.0 }
+    }).collect()
+}
+
+fn colliders(stads: &[Stad]) -> Vec<Collider> {
+    stads.iter().enumerate().map(|(i, s)| {
+	let shape = if i % 4 == 0 {
+	    Shape::Circle(Circle { p: s.p1, r: s.r })
+	} else {
+	    Shape::Stad(*s)
+	};
+	Collider::new(shape, CollisionFilter::default(), i as u64)
+    }).collect()
+}
+
+const SIZES: [usize; 4] = [100, 400, 1600, 6400];
+
+fn sweep_and_prune(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broadphase");
+    for &n in SIZES.iter() {
+	let colliders = colliders(&scene(n));
+	group.throughput(Throughput::Elements(n as u64));
+	group.bench_with_input(BenchmarkId::new("candidate_pairs", n), &colliders, |bench, cs| {
+	    bench.iter(|| broadphase::candidate_pairs(black_box(cs)))
+	});
+	group.bench_with_input(BenchmarkId::new("colliding_pairs", n), &colliders, |bench, cs| {
+	    bench.iter(|| broadphase::colliding_pairs(black_box(cs)))
+	});
+    }
+    group.finish();
+}
+
+// building the level quadtree, and then asking it about one collider
+fn static_geometry(c: &mut Criterion) {
+    let mut group = c.benchmark_group("static_geometry");
+    for &n in SIZES.iter() {
+	let walls = scene(n);
+	group.bench_with_input(BenchmarkId::new("build", n), &walls, |bench, walls| {
+	    bench.iter(|| StaticGeometry::new(black_box(walls), CollisionFilter::default()))
+	});
+	let level = StaticGeometry::new(&walls, CollisionFilter::default());
+	let probe = Collider::new(Shape::Circle(Circle { p: walls[0].p1, r: 20.0 }), CollisionFilter::default(), 0);
+	group.bench_with_input(BenchmarkId::new("overlapping", n), &level, |bench, level| {
+	    bench.iter(|| level.overlapping(black_box(&probe)))
+	});
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sweep_and_prune, static_geometry);
+criterion_main!(benches);
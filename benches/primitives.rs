@@ -0,0 +1,114 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use stad_demos::geometry::*;
+use stad_demos::simd::StadBatch;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::Lcg;
+
+
+// Pairs of stadiums in each of the layouts the closest point math branches
+// on. The radii are small enough that only crossing pairs collide.
+fn stad_pairs() -> Vec<(&'static str, Stad, Stad)> {
+    vec![
+	("parallel",     Stad::new(0.0, 0.0, 100.0, 37.0, 5.0),   Stad::new(10.0, 40.0, 110.0, 77.0, 5.0)),
+	("crossing",     Stad::new(0.0, 0.0, 100.0, 100.0, 5.0),  Stad::new(0.0, 100.0, 100.0, 0.0, 5.0)),
+	("disjoint",     Stad::new(0.0, 0.0, 50.0, 20.0, 5.0),    Stad::new(200.0, 150.0, 260.0, 90.0, 5.0)),
+	("axis_aligned", Stad::new(0.0, 0.0, 100.0, 0.0, 5.0),    Stad::new(50.0, 20.0, 50.0, 120.0, 5.0)),
+    ]
+}
+
+// a point beside the segment, one past its end, and one beside an axis
+// aligned segment
+fn point_cases() -> Vec<(&'static str, Segment, Point)> {
+    let slanted = Segment { p1: Point{x: 0.0, y: 0.0}, p2: Point{x: 100.0, y: 37.0} };
+    let flat = Segment { p1: Point{x: 0.0, y: 0.0}, p2: Point{x: 100.0, y: 0.0} };
+    vec![
+	("beside",       slanted, Point{x: 40.0, y: 60.0}),
+	("past_end",     slanted, Point{x: 150.0, y: 70.0}),
+	("axis_aligned", flat,    Point{x: 40.0, y: 60.0}),
+    ]
+}
+
+fn stad_stad(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stad_stad");
+    for (name, a, b) in stad_pairs() {
+	group.bench_function(format!("collides/{}", name), |bench| {
+	    bench.iter(|| black_box(a).collides_stad(black_box(b)))
+	});
+	group.bench_function(format!("gap/{}", name), |bench| {
+	    bench.iter(|| black_box(a).gap_stad(black_box(b)))
+	});
+    }
+    group.finish();
+}
+
+fn segment_segment(c: &mut Criterion) {
+    let mut group = c.benchmark_group("segment_segment");
+    for (name, a, b) in stad_pairs() {
+	let (a, b) = (a.segment(), b.segment());
+	group.bench_function(format!("distance_to_segment/{}", name), |bench| {
+	    bench.iter(|| black_box(a).distance_to_segment(black_box(b)))
+	});
+	group.bench_function(format!("closest_points/{}", name), |bench| {
+	    bench.iter(|| black_box(a).closest_points(black_box(b)))
+	});
+	group.bench_function(format!("is_overlap/{}", name), |bench| {
+	    bench.iter(|| black_box(a).is_overlap(black_box(b)))
+	});
+    }
+    group.finish();
+}
+
+fn point_segment(c: &mut Criterion) {
+    let mut group = c.benchmark_group("point_segment");
+    for (name, s, p) in point_cases() {
+	group.bench_function(format!("distance_to_point/{}", name), |bench| {
+	    bench.iter(|| black_box(s).distance_to_point(black_box(p)))
+	});
+	group.bench_function(format!("closest_point/{}", name), |bench| {
+	    bench.iter(|| black_box(s).closest_point(black_box(p)))
+	});
+    }
+    group.finish();
+}
+
+fn circle_queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circle");
+    for (name, s, p) in point_cases() {
+	let circle = Circle { p, r: 10.0 };
+	let stad = Stad { p1: s.p1, p2: s.p2, r: 5.0 };
+	group.bench_function(format!("collides_stad/{}", name), |bench| {
+	    bench.iter(|| black_box(circle).collides_stad(black_box(stad)))
+	});
+    }
+    let a = Circle { p: Point{x: 0.0, y: 0.0}, r: 10.0 };
+    let b = Circle { p: Point{x: 15.0, y: 8.0}, r: 10.0 };
+    group.bench_function("collides_circle", |bench| {
+	bench.iter(|| black_box(a).collides_circle(black_box(b)))
+    });
+    group.finish();
+}
+
+// One stadium against a thousand, through the batch kernel and through the
+// scalar test in a loop
+fn batch(c: &mut Criterion) {
+    let mut rng = Lcg(28);
+    let stads: Vec<Stad> = (0..1024).map(|_| rng.stad(-100.0, 100.0, 20.0)).collect();
+    let batch = StadBatch::from_stads(&stads);
+    let probe = rng.stad(-100.0, 100.0, 20.0);
+    let mut group = c.benchmark_group("batch");
+    group.bench_function("collides_stad/batch", |bench| {
+	bench.iter(|| batch.collides_stad(black_box(probe)))
+    });
+    group.bench_function("collides_stad/scalar", |bench| {
+	bench.iter(|| stads.iter().map(|s| black_box(probe).collides_stad(*s)).collect::<Vec<bool>>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, stad_stad, segment_segment, point_segment, circle_queries, batch);
+criterion_main!(benches);
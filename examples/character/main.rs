@@ -0,0 +1,245 @@
+use ggez::{Context, ContextBuilder, GameResult, conf, timer};
+use ggez::event::{self, EventHandler, KeyCode, KeyMods};
+use ggez::graphics;
+use ggez::graphics::{DrawMode};
+use ggez::input::keyboard;
+use ggez::mint::Point2;
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::static_geometry::*;
+use stad_demos::character::*;
+use stad_demos::world::*;
+use stad_demos::sensor::*;
+use stad_demos::stepper::*;
+
+static WINDOW_WIDTH : f64 = 1200.0;
+static WINDOW_HEIGHT: f64 = 800.0;
+
+// physics runs at this rate whatever the frame rate
+static PHYSICS_STEP: f64 = 1.0/60.0;
+static GRAVITY: f64 = 1400.0;
+static RUN_SPEED: f64 = 300.0;
+static JUMP_SPEED: f64 = 650.0;
+
+// user_data tags for the sensors
+static COIN: u64 = 1;
+static WATER: u64 = 2;
+
+
+struct MyGame {
+    walls: StaticGeometry,
+    player: CharacterController,
+    timestep: FixedTimestep,
+    // the player before the last step, for drawing in between
+    previous: Stad,
+    fall_speed: f64,
+    // coins and zones; the player has a copy of its collider in here so the
+    // sensors can see it
+    sensors: World,
+    player_handle: Handle,
+    coins: usize,
+    in_water: bool,
+}
+
+ 
+fn main() -> Result<(), ggez::GameError> {
+
+    let my_window_settings = conf::WindowSetup {
+	title: "Character Controller".to_owned(),
+	samples: conf::NumSamples::One,
+	vsync: true,
+	icon: "".to_owned(),
+	srgb: true,
+    };
+
+    let my_window_mode = conf::WindowMode {
+	width: WINDOW_WIDTH as f32 + 10.0,
+	height: WINDOW_HEIGHT as f32 + 10.0,
+	maximized: false,
+	fullscreen_type: conf::FullscreenType::Windowed,
+	borderless: false,
+	min_width: 0.0,
+	max_width: 0.0,
+	min_height: 0.0,
+	max_height: 0.0,
+	resizable: false,
+	visible: true,
+	resize_on_scale_factor_change: false,
+    };
+    
+    // Make a Context and an EventLoop.
+    let (mut ctx, event_loop) =
+       ContextBuilder::new("game_name", "author_name")
+	.window_setup(my_window_settings)
+	.window_mode(my_window_mode)
+        .build()
+        .unwrap();
+
+    let my_game = MyGame::new(&mut ctx);
+
+    // Run!
+    event::run(ctx, event_loop, my_game)
+}
+
+impl MyGame {
+    pub fn new(_ctx: &mut Context) -> MyGame {
+	let level = [
+	    // floor and outer walls
+	    Stad::new(20.0,760.0, 1180.0,760.0, 20.0),
+	    Stad::new(20.0,100.0, 20.0,760.0, 20.0),
+	    Stad::new(1180.0,100.0, 1180.0,760.0, 20.0),
+	    // a gentle slope (walkable) and a steep one (not)
+	    Stad::new(150.0,740.0, 400.0,620.0, 10.0),
+	    Stad::new(850.0,740.0, 920.0,520.0, 10.0),
+	    // stairs low enough to step up
+	    Stad::new(500.0,735.0, 560.0,735.0, 5.0),
+	    Stad::new(560.0,725.0, 620.0,725.0, 5.0),
+	    Stad::new(620.0,715.0, 680.0,715.0, 5.0),
+	    // platforms to jump onto
+	    Stad::new(400.0,620.0, 600.0,620.0, 10.0),
+	    Stad::new(650.0,500.0, 800.0,500.0, 10.0),
+	    Stad::new(950.0,420.0, 1100.0,420.0, 10.0),
+	];
+	let player = CharacterController::new(Stad::new(100.0,600.0, 100.0,640.0, 20.0),
+					      CharacterSettings::default());
+	let mut sensors = World::new();
+	let player_handle = sensors.add(player.collider());
+	for &(x, y) in [(500.0, 580.0), (725.0, 460.0), (1025.0, 380.0), (300.0, 620.0)].iter() {
+	    let coin = Circle { p: Point{x, y}, r: 12.0 };
+	    sensors.add(Collider::sensor(coin.into(), CollisionFilter::default(), COIN));
+	}
+	let pool = Stad::new(960.0,715.0, 1140.0,715.0, 25.0);
+	sensors.add(Collider::sensor(pool.into(), CollisionFilter::default(), WATER));
+        MyGame {
+	    walls: StaticGeometry::new(&level, CollisionFilter::default()),
+	    previous: player.stad(),
+	    player,
+	    timestep: FixedTimestep::new(PHYSICS_STEP),
+	    fall_speed: 0.0,
+	    sensors,
+	    player_handle,
+	    coins: 0,
+	    in_water: false,
+	}
+    }
+
+    fn step(&mut self, ctx: &Context, dt: f64) {
+	self.previous = self.player.stad();
+
+	let mut run = 0.0;
+	if keyboard::is_key_pressed(ctx, KeyCode::Left) || keyboard::is_key_pressed(ctx, KeyCode::A) {
+	    run -= RUN_SPEED;
+	}
+	if keyboard::is_key_pressed(ctx, KeyCode::Right) || keyboard::is_key_pressed(ctx, KeyCode::D) {
+	    run += RUN_SPEED;
+	}
+	self.fall_speed += GRAVITY * dt;
+
+	let motion = Vector{x: run*dt, y: self.fall_speed*dt};
+	let moved = self.player.move_by(&self.walls, motion);
+	// landed, or bumped our head
+	if (self.player.on_ground && self.fall_speed > 0.0) || moved.y > motion.y + 1e-6 {
+	    self.fall_speed = 0.0;
+	}
+
+	self.sensors.set_shape(self.player_handle, self.player.stad().into());
+	for event in self.sensors.update_sensors() {
+	    let tag = match self.sensors.get(event.sensor) {
+		Some(c) => c.user_data,
+		None => continue, // a coin picked up last step, now exiting
+	    };
+	    match event.kind {
+		SensorEventKind::Enter if tag == COIN => {
+		    self.sensors.remove(event.sensor);
+		    self.coins += 1;
+		},
+		SensorEventKind::Enter if tag == WATER => self.in_water = true,
+		SensorEventKind::Exit if tag == WATER => self.in_water = false,
+		_ => (),
+	    }
+	}
+    }
+}
+
+
+
+fn stad_mesh(ctx: &mut Context, s: Stad, color: graphics::Color)
+	     -> GameResult<graphics::Mesh> {
+    let stroke = match DrawMode::stroke((s.r*2.0) as f32) {
+	DrawMode::Stroke(so) => {
+	    so.with_start_cap(graphics::LineCap::Round)
+		.with_end_cap(graphics::LineCap::Round)
+	},
+	DrawMode::Fill(_) => panic!("unreachable"),
+    };
+    graphics::Mesh::new_polyline(ctx, DrawMode::Stroke(stroke),
+				 &[s.p1.mint(), s.p2.mint()], color)
+}
+
+impl EventHandler<ggez::GameError> for MyGame {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+	let mut timestep = self.timestep;
+	timestep.advance(timer::delta(ctx).as_secs_f64(), |dt| self.step(ctx, dt));
+	self.timestep = timestep;
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        // Clear
+        graphics::clear(ctx, graphics::Color::WHITE);
+
+	let gray = graphics::Color::new(0.5, 0.5, 0.5, 0.9);
+	for wall in self.walls.walls().iter() {
+	    let mesh = stad_mesh(ctx, *wall, gray)?;
+	    graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+	// Draw the sensors
+	for (_, c) in self.sensors.iter().filter(|(_, c)| c.sensor) {
+	    let mesh = match c.shape {
+		Shape::Circle(coin) => graphics::Mesh::new_circle
+		    (ctx, DrawMode::fill(), coin.p.mint(), coin.r as f32, 0.5,
+		     graphics::Color::new(0.95, 0.8, 0.1, 0.9))?,
+		Shape::Stad(pool) => stad_mesh(ctx, pool, graphics::Color::new(0.2, 0.4, 0.9, 0.4))?,
+	    };
+	    graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+	let score = graphics::Text::new(format!("coins: {}", self.coins));
+	graphics::draw(ctx, &score, (Point2::<f32>{x:40.0, y:20.0}, graphics::Color::BLACK))?;
+
+	// green on the ground, orange in the air, blue in the water
+	let color = if self.in_water {
+	    graphics::Color::new(0.2, 0.35, 0.75, 0.8)
+	} else if self.player.on_ground {
+	    graphics::Color::new(0.05, 0.7, 0.25, 0.8)
+	} else {
+	    graphics::Color::new(0.7, 0.45, 0.05, 0.8)
+	};
+	let player = self.previous.lerp(self.player.stad(), self.timestep.alpha());
+	let mesh = stad_mesh(ctx, player, color)?;
+	graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+
+	// Draw the ground normal
+	if let Some(n) = self.player.ground_normal {
+	    let feet = player.p2;
+	    let purple = graphics::Color::new(0.5, 0.0, 0.8, 0.9);
+	    let line = graphics::Mesh::new_line
+		(ctx, &[feet.mint(), feet.plus(n.scaled(40.0)).mint()], 4.0, purple)?;
+	    graphics::draw(ctx, &line, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+        graphics::present(ctx)
+    }
+
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: KeyCode,
+		      _keymods: KeyMods, _repeat: bool) {
+	match keycode {
+	    KeyCode::Space | KeyCode::Up | KeyCode::W if self.player.on_ground => {
+		self.fall_speed = -JUMP_SPEED;
+	    },
+	    KeyCode::Escape => event::quit(ctx),
+	    _ => (),
+	}
+    }
+}
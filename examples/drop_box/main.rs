@@ -0,0 +1,170 @@
+use ggez::{Context, ContextBuilder, GameResult, conf, timer};
+use ggez::event::{self, EventHandler};
+use ggez::graphics;
+use ggez::graphics::{DrawMode};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::dynamics::*;
+use stad_demos::stepper::*;
+
+static WINDOW_WIDTH : f64 = 1200.0;
+static WINDOW_HEIGHT: f64 = 800.0;
+
+// the longest step we'll take in one go, so a slow frame can't launch things
+// through the walls
+// physics runs at this rate whatever the frame rate
+static PHYSICS_STEP: f64 = 1.0/60.0;
+
+
+struct MyGame {
+    sim: Simulation,
+    clock: SimulationClock,
+    dropped: usize,
+}
+
+ 
+fn main() -> Result<(), ggez::GameError> {
+
+    let my_window_settings = conf::WindowSetup {
+	title: "Drop Box".to_owned(),
+	samples: conf::NumSamples::One,
+	vsync: true,
+	icon: "".to_owned(),
+	srgb: true,
+    };
+
+    let my_window_mode = conf::WindowMode {
+	width: WINDOW_WIDTH as f32 + 10.0,
+	height: WINDOW_HEIGHT as f32 + 10.0,
+	maximized: false,
+	fullscreen_type: conf::FullscreenType::Windowed,
+	borderless: false,
+	min_width: 0.0,
+	max_width: 0.0,
+	min_height: 0.0,
+	max_height: 0.0,
+	resizable: false,
+	visible: true,
+	resize_on_scale_factor_change: false,
+    };
+    
+    // Make a Context and an EventLoop.
+    let (mut ctx, event_loop) =
+       ContextBuilder::new("game_name", "author_name")
+	.window_setup(my_window_settings)
+	.window_mode(my_window_mode)
+        .build()
+        .unwrap();
+
+    let my_game = MyGame::new(&mut ctx);
+
+    // Run!
+    event::run(ctx, event_loop, my_game)
+}
+
+impl MyGame {
+    pub fn new(_ctx: &mut Context) -> MyGame {
+	let mut sim = Simulation::new(Vector{x: 0.0, y: 900.0});
+	// the box: a floor and two walls
+	for s in [Stad::new(100.0,750.0, 1100.0,750.0, 20.0),
+		  Stad::new(100.0,150.0, 100.0,750.0, 20.0),
+		  Stad::new(1100.0,150.0, 1100.0,750.0, 20.0)].iter() {
+	    sim.add_body(RigidBody::fixed(Collider::stad(*s)));
+	}
+        let mut game = MyGame {
+	    sim,
+	    clock: SimulationClock::new(PHYSICS_STEP),
+	    dropped: 0,
+	};
+	for n in 0..6 {
+	    game.drop_shape(Point{x: 250.0 + 150.0*n as f64, y: 200.0 + 40.0*n as f64});
+	}
+	game
+    }
+
+    // cycles through a few sizes of stadium and circle so every click gives
+    // something a little different
+    fn drop_shape(&mut self, p: Point) {
+	let n = self.dropped;
+	self.dropped += 1;
+	let collider = if n % 3 == 2 {
+	    Collider::circle(Circle { p, r: 20.0 + 10.0*(n % 4) as f64 })
+	} else {
+	    let half = 30.0 + 15.0*(n % 5) as f64;
+	    let tilt = Vector{x: half, y: 0.0}.rotated(0.3*n as f64);
+	    Collider::stad(Stad {
+		p1: p.minus(tilt),
+		p2: p.plus(tilt),
+		r: 12.0 + 4.0*(n % 3) as f64,
+	    })
+	};
+	self.sim.add_body(RigidBody::new(collider, 1.0));
+    }
+}
+
+
+
+fn shape_mesh(ctx: &mut Context, shape: Shape, color: graphics::Color)
+	      -> GameResult<graphics::Mesh> {
+    match shape {
+	Shape::Circle(c) => graphics::Mesh::new_circle
+	    (ctx, DrawMode::fill(), c.p.mint(), c.r as f32, 0.5, color),
+	Shape::Stad(s) => {
+	    let stroke = match DrawMode::stroke((s.r*2.0) as f32) {
+		DrawMode::Stroke(so) => {
+		    so.with_start_cap(graphics::LineCap::Round)
+			.with_end_cap(graphics::LineCap::Round)
+		},
+		DrawMode::Fill(_) => panic!("unreachable"),
+	    };
+	    graphics::Mesh::new_polyline(ctx, DrawMode::Stroke(stroke),
+					 &[s.p1.mint(), s.p2.mint()], color)
+	},
+    }
+}
+
+impl EventHandler<ggez::GameError> for MyGame {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+	self.clock.update(&mut self.sim, timer::delta(ctx).as_secs_f64());
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        // Clear
+        graphics::clear(ctx, graphics::Color::WHITE);
+
+	let gray = graphics::Color::new(0.5, 0.5, 0.5, 0.9);
+	let green = graphics::Color::new(0.05, 0.7, 0.25, 0.8);
+	let blue = graphics::Color::new(0.2, 0.35, 0.75, 0.8);
+	for (n, body) in self.sim.bodies.iter().enumerate() {
+	    let color = if body.is_fixed() {
+		gray
+	    } else if body.asleep {
+		blue
+	    } else {
+		green
+	    };
+	    let mesh = shape_mesh(ctx, self.clock.shape(&self.sim, n), color)?;
+	    graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+	// Draw the contact points
+	let orange = graphics::Color::new(0.9, 0.5, 0.1, 0.9);
+	for c in self.sim.contacts.iter() {
+	    let dot = graphics::Mesh::new_circle
+		(ctx, DrawMode::fill(), c.contact.point.mint(), 4.0, 0.5, orange)?;
+	    graphics::draw(ctx, &dot, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+        graphics::present(ctx)
+    }
+
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context,
+			       _button: MouseButton, x: f32, y: f32) {
+	self.drop_shape(Point{ x:x as f64, y:y as f64});
+    }
+}
@@ -0,0 +1,259 @@
+use ggez::{Context, ContextBuilder, GameResult, conf, timer};
+use ggez::event::{self, EventHandler};
+use ggez::graphics;
+use ggez::graphics::{DrawMode};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::dynamics::*;
+use stad_demos::stepper::*;
+use stad_demos::joint::*;
+
+static WINDOW_WIDTH : f64 = 1200.0;
+static WINDOW_HEIGHT: f64 = 800.0;
+
+// physics runs at this rate whatever the frame rate
+static PHYSICS_STEP: f64 = 1.0/60.0;
+
+// walls are on the first layer, ragdoll parts on the second and only touch
+// walls, so limbs can overlap where they're pinned together
+static WALLS: u32 = 1;
+static LIMBS: u32 = 2;
+
+
+struct MyGame {
+    sim: Simulation,
+    clock: SimulationClock,
+    // a fixed body that follows the mouse, and the joint pinning a limb to it
+    cursor: usize,
+    grab: Option<usize>,
+}
+
+ 
+fn main() -> Result<(), ggez::GameError> {
+
+    let my_window_settings = conf::WindowSetup {
+	title: "Ragdoll".to_owned(),
+	samples: conf::NumSamples::One,
+	vsync: true,
+	icon: "".to_owned(),
+	srgb: true,
+    };
+
+    let my_window_mode = conf::WindowMode {
+	width: WINDOW_WIDTH as f32 + 10.0,
+	height: WINDOW_HEIGHT as f32 + 10.0,
+	maximized: false,
+	fullscreen_type: conf::FullscreenType::Windowed,
+	borderless: false,
+	min_width: 0.0,
+	max_width: 0.0,
+	min_height: 0.0,
+	max_height: 0.0,
+	resizable: false,
+	visible: true,
+	resize_on_scale_factor_change: false,
+    };
+    
+    // Make a Context and an EventLoop.
+    let (mut ctx, event_loop) =
+       ContextBuilder::new("game_name", "author_name")
+	.window_setup(my_window_settings)
+	.window_mode(my_window_mode)
+	.build()
+	.unwrap();
+
+    let my_game = MyGame::new(&mut ctx);
+
+    // Run!
+    event::run(ctx, event_loop, my_game)
+}
+
+fn limb(sim: &mut Simulation, x1: f64, y1: f64, x2: f64, y2: f64, r: f64) -> usize {
+    let stad = Stad { p1: Point{x: x1, y: y1}, p2: Point{x: x2, y: y2}, r };
+    let collider = Collider::new(Shape::Stad(stad), CollisionFilter::new(LIMBS, WALLS), 0);
+    sim.add_body(RigidBody::new(collider, 1.0))
+}
+
+fn pin(sim: &mut Simulation, a: usize, b: usize, x: f64, y: f64) {
+    let joint = Joint::revolute(&sim.bodies, a, b, Point{x, y});
+    sim.add_joint(joint);
+}
+
+fn add_ragdoll(sim: &mut Simulation, x: f64, y: f64) {
+    let torso = limb(sim, x, y, x, y + 80.0, 18.0);
+    let head = sim.add_body(RigidBody::new(Collider::new(
+	Shape::Circle(Circle { p: Point{x, y: y - 38.0}, r: 20.0 }),
+	CollisionFilter::new(LIMBS, WALLS), 0), 1.0));
+    let neck = Joint::weld(&sim.bodies, torso, head, Point{x, y: y - 15.0});
+    sim.add_joint(neck);
+
+    for side in [-1.0, 1.0].iter() {
+	let upper_arm = limb(sim, x, y + 5.0, x + side*45.0, y + 35.0, 9.0);
+	let lower_arm = limb(sim, x + side*45.0, y + 35.0, x + side*85.0, y + 65.0, 8.0);
+	pin(sim, torso, upper_arm, x, y + 5.0);
+	pin(sim, upper_arm, lower_arm, x + side*45.0, y + 35.0);
+
+	let upper_leg = limb(sim, x, y + 80.0, x + side*15.0, y + 140.0, 11.0);
+	let lower_leg = limb(sim, x + side*15.0, y + 140.0, x + side*20.0, y + 200.0, 10.0);
+	pin(sim, torso, upper_leg, x, y + 80.0);
+	pin(sim, upper_leg, lower_leg, x + side*15.0, y + 140.0);
+    }
+}
+
+impl MyGame {
+    pub fn new(_ctx: &mut Context) -> MyGame {
+	let mut sim = Simulation::new(Vector{x: 0.0, y: 900.0});
+	let wall = |s: Stad| RigidBody::fixed(Collider::new(Shape::Stad(s), CollisionFilter::new(WALLS, CollisionFilter::ALL), 0));
+	for s in [Stad::new(40.0,760.0, 1160.0,760.0, 20.0),
+		  Stad::new(40.0,100.0, 40.0,760.0, 20.0),
+		  Stad::new(1160.0,100.0, 1160.0,760.0, 20.0)].iter() {
+	    sim.add_body(wall(*s));
+	}
+
+	add_ragdoll(&mut sim, 400.0, 250.0);
+
+	// a lamp swinging on a cord from the ceiling
+	let hook = sim.add_body(wall(Stad::new(800.0,60.0, 900.0,60.0, 6.0)));
+	let lamp = limb(&mut sim, 850.0, 250.0, 850.0, 300.0, 20.0);
+	let cord = Joint::distance(&sim.bodies, hook, lamp, Point{x: 850.0, y: 60.0}, Point{x: 850.0, y: 250.0});
+	sim.add_joint(cord);
+
+	// a block sliding along a rail
+	let rail = sim.add_body(wall(Stad::new(150.0,120.0, 700.0,120.0, 6.0)));
+	let slider = limb(&mut sim, 420.0, 150.0, 480.0, 150.0, 16.0);
+	let track = Joint::prismatic(&sim.bodies, rail, slider, Point{x: 450.0, y: 150.0}, Vector{x: 1.0, y: 0.0});
+	sim.add_joint(track);
+
+	// the cursor touches nothing (empty mask)
+	let cursor = sim.add_body(RigidBody::fixed(Collider::new(
+	    Shape::Circle(Circle { p: Point{x: 0.0, y: 0.0}, r: 1.0 }),
+	    CollisionFilter::new(0, 0), 0)));
+
+	MyGame {
+	    sim,
+	    clock: SimulationClock::new(PHYSICS_STEP),
+	    cursor,
+	    grab: None,
+	}
+    }
+
+    // every stadium endpoint that can be grabbed, with the body it belongs to
+    fn handles(&self) -> Vec<(usize, Point)> {
+	let mut v = vec![];
+	for (n, body) in self.sim.bodies.iter().enumerate().filter(|(_, b)| !b.is_fixed()) {
+	    if let Shape::Stad(s) = body.shape() {
+		v.push((n, s.p1));
+		v.push((n, s.p2));
+	    }
+	}
+	v
+    }
+}
+
+
+
+fn shape_mesh(ctx: &mut Context, shape: Shape, color: graphics::Color)
+	      -> GameResult<graphics::Mesh> {
+    match shape {
+	Shape::Circle(c) => graphics::Mesh::new_circle
+	    (ctx, DrawMode::fill(), c.p.mint(), c.r as f32, 0.5, color),
+	Shape::Stad(s) => {
+	    let stroke = match DrawMode::stroke((s.r*2.0) as f32) {
+		DrawMode::Stroke(so) => {
+		    so.with_start_cap(graphics::LineCap::Round)
+			.with_end_cap(graphics::LineCap::Round)
+		},
+		DrawMode::Fill(_) => panic!("unreachable"),
+	    };
+	    graphics::Mesh::new_polyline(ctx, DrawMode::Stroke(stroke),
+					 &[s.p1.mint(), s.p2.mint()], color)
+	},
+    }
+}
+
+impl EventHandler<ggez::GameError> for MyGame {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+	self.clock.update(&mut self.sim, timer::delta(ctx).as_secs_f64());
+	Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+	// Clear
+	graphics::clear(ctx, graphics::Color::WHITE);
+
+	let gray = graphics::Color::new(0.5, 0.5, 0.5, 0.9);
+	let green = graphics::Color::new(0.05, 0.7, 0.25, 0.8);
+	let blue = graphics::Color::new(0.2, 0.35, 0.75, 0.8);
+	for (n, body) in self.sim.bodies.iter().enumerate() {
+	    if n == self.cursor {
+		continue;
+	    }
+	    let color = if body.is_fixed() {
+		gray
+	    } else if body.asleep {
+		blue
+	    } else {
+		green
+	    };
+	    let mesh = shape_mesh(ctx, self.clock.shape(&self.sim, n), color)?;
+	    graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+	// Draw the joints
+	let purple = graphics::Color::new(0.5, 0.0, 0.8, 0.9);
+	for joint in self.sim.joints.iter() {
+	    let (pa, pb) = joint.world_anchors(&self.sim.bodies);
+	    if pa.distance(pb) > 1.0 {
+		let line = graphics::Mesh::new_line(ctx, &[pa.mint(), pb.mint()], 3.0, purple)?;
+		graphics::draw(ctx, &line, (Point2::<f32>{x:0.0, y:0.0},))?;
+	    }
+	    let dot = graphics::Mesh::new_circle(ctx, DrawMode::fill(), pb.mint(), 4.0, 0.5, purple)?;
+	    graphics::draw(ctx, &dot, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+	// Draw selector indicators
+	for (_, p) in self.handles() {
+	    let selec = graphics::Mesh::new_circle
+		(ctx, DrawMode::stroke(2.0), p.mint(), 15.0, 1.0,
+		 graphics::Color::BLACK)?;
+	    graphics::draw(ctx, &selec, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+	graphics::present(ctx)
+    }
+
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context,
+			       _button: MouseButton, x: f32, y: f32) {
+	let mouse = Point{ x:x as f64, y:y as f64};
+	for (n, p) in self.handles() {
+	    if p.distance(mouse) < 15.0 {
+		self.sim.bodies[self.cursor].position = p;
+		self.sim.bodies[n].wake();
+		let joint = Joint::revolute(&self.sim.bodies, self.cursor, n, p);
+		self.grab = Some(self.sim.add_joint(joint));
+		return;
+	    }
+	}
+    }
+
+    fn mouse_button_up_event(&mut self, _ctx: &mut Context,
+			       _button: MouseButton, _x: f32, _y: f32) {
+	if let Some(j) = self.grab.take() {
+	    self.sim.joints.remove(j);
+	}
+    }
+
+    fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32,
+			  _xrel: f32, _yrel: f32) {
+	self.sim.bodies[self.cursor].position = Point{ x:x as f64, y:y as f64};
+	// the cursor is fixed, so it wouldn't wake what it's holding on its own
+	if let Some(j) = self.grab {
+	    let held = self.sim.joints[j].b;
+	    self.sim.bodies[held].wake();
+	}
+    }
+}
@@ -0,0 +1,204 @@
+use ggez::{Context, ContextBuilder, GameResult, conf, timer};
+use ggez::event::{self, EventHandler};
+use ggez::graphics;
+use ggez::graphics::{DrawMode};
+use ggez::input::mouse::MouseButton;
+use ggez::mint::Point2;
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::static_geometry::*;
+use stad_demos::soft::*;
+use stad_demos::stepper::*;
+
+static WINDOW_WIDTH : f64 = 1200.0;
+static WINDOW_HEIGHT: f64 = 800.0;
+
+// physics runs at this rate whatever the frame rate
+static PHYSICS_STEP: f64 = 1.0/60.0;
+
+
+struct MyGame {
+    walls: StaticGeometry,
+    sim: SoftSimulation,
+    timestep: FixedTimestep,
+    // every particle's position before the last step, for drawing in between
+    previous: Vec<Vec<Point>>,
+    // the particle being dragged (chain, particle) and its inverse mass from
+    // before it was picked up
+    grab: Option<(usize, usize, f64)>,
+}
+
+ 
+fn main() -> Result<(), ggez::GameError> {
+
+    let my_window_settings = conf::WindowSetup {
+	title: "Soft Stadiums".to_owned(),
+	samples: conf::NumSamples::One,
+	vsync: true,
+	icon: "".to_owned(),
+	srgb: true,
+    };
+
+    let my_window_mode = conf::WindowMode {
+	width: WINDOW_WIDTH as f32 + 10.0,
+	height: WINDOW_HEIGHT as f32 + 10.0,
+	maximized: false,
+	fullscreen_type: conf::FullscreenType::Windowed,
+	borderless: false,
+	min_width: 0.0,
+	max_width: 0.0,
+	min_height: 0.0,
+	max_height: 0.0,
+	resizable: false,
+	visible: true,
+	resize_on_scale_factor_change: false,
+    };
+    
+    // Make a Context and an EventLoop.
+    let (mut ctx, event_loop) =
+       ContextBuilder::new("game_name", "author_name")
+	.window_setup(my_window_settings)
+	.window_mode(my_window_mode)
+	.build()
+	.unwrap();
+
+    let my_game = MyGame::new(&mut ctx);
+
+    // Run!
+    event::run(ctx, event_loop, my_game)
+}
+
+impl MyGame {
+    pub fn new(_ctx: &mut Context) -> MyGame {
+	let level = [
+	    // floor and outer walls
+	    Stad::new(20.0,760.0, 1180.0,760.0, 20.0),
+	    Stad::new(20.0,100.0, 20.0,760.0, 20.0),
+	    Stad::new(1180.0,100.0, 1180.0,760.0, 20.0),
+	    // a ramp and a ledge to squish against
+	    Stad::new(150.0,500.0, 550.0,650.0, 10.0),
+	    Stad::new(700.0,560.0, 900.0,560.0, 10.0),
+	];
+	let mut sim = SoftSimulation::new(Vector{x: 0.0, y: 900.0});
+	sim.add_chain(SoftChain::blob(Point{x: 250.0, y: 300.0}, 60.0, 16, 10.0));
+	sim.add_chain(SoftChain::blob(Point{x: 400.0, y: 150.0}, 45.0, 12, 10.0));
+	let mut squishy = SoftChain::blob(Point{x: 800.0, y: 300.0}, 70.0, 20, 8.0);
+	squishy.stiffness = 0.15;
+	sim.add_chain(squishy);
+
+	// a rope hanging from the ceiling
+	let points: Vec<_> = (0..12).map(|i| Point{x: 1000.0, y: 100.0 + 20.0*i as f64}).collect();
+	let mut rope = SoftChain::rope(&points, 6.0);
+	rope.particles[0].inv_mass = 0.0;
+	sim.add_chain(rope);
+
+	MyGame {
+	    walls: StaticGeometry::new(&level, CollisionFilter::default()),
+	    sim,
+	    timestep: FixedTimestep::new(PHYSICS_STEP),
+	    previous: vec![],
+	    grab: None,
+	}
+    }
+
+    fn drawn_position(&self, c: usize, i: usize) -> Point {
+	let now = self.sim.chains[c].particles[i].position;
+	match self.previous.get(c).and_then(|ps| ps.get(i)) {
+	    Some(before) => before.lerp(now, self.timestep.alpha()),
+	    None => now,
+	}
+    }
+}
+
+
+
+fn stad_mesh(ctx: &mut Context, s: Stad, color: graphics::Color)
+	     -> GameResult<graphics::Mesh> {
+    let stroke = match DrawMode::stroke((s.r*2.0) as f32) {
+	DrawMode::Stroke(so) => {
+	    so.with_start_cap(graphics::LineCap::Round)
+		.with_end_cap(graphics::LineCap::Round)
+	},
+	DrawMode::Fill(_) => panic!("unreachable"),
+    };
+    graphics::Mesh::new_polyline(ctx, DrawMode::Stroke(stroke),
+				 &[s.p1.mint(), s.p2.mint()], color)
+}
+
+impl EventHandler<ggez::GameError> for MyGame {
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+	let (sim, walls, previous) = (&mut self.sim, &self.walls, &mut self.previous);
+	self.timestep.advance(timer::delta(ctx).as_secs_f64(), |dt| {
+	    *previous = sim.chains.iter()
+		.map(|c| c.particles.iter().map(|p| p.position).collect())
+		.collect();
+	    sim.step(walls, dt);
+	});
+	Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+	// Clear
+	graphics::clear(ctx, graphics::Color::WHITE);
+
+	let gray = graphics::Color::new(0.5, 0.5, 0.5, 0.9);
+	for wall in self.walls.walls().iter() {
+	    let mesh = stad_mesh(ctx, *wall, gray)?;
+	    graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+	}
+
+	let green = graphics::Color::new(0.05, 0.7, 0.25, 0.8);
+	for (c, chain) in self.sim.chains.iter().enumerate() {
+	    for s in 0..chain.segment_count() {
+		let (a, b) = chain.segment_ends(s);
+		let (p1, p2) = (self.drawn_position(c, a), self.drawn_position(c, b));
+		let mesh = stad_mesh(ctx, Stad { p1, p2, r: chain.radius }, green)?;
+		graphics::draw(ctx, &mesh, (Point2::<f32>{x:0.0, y:0.0},))?;
+	    }
+	}
+
+	// Draw the particles, the grabbed one bigger
+	for (c, chain) in self.sim.chains.iter().enumerate() {
+	    for i in 0..chain.particles.len() {
+		let held = matches!(self.grab, Some((gc, gi, _)) if gc == c && gi == i);
+		let r = if held { 8.0 } else { 3.0 };
+		let dot = graphics::Mesh::new_circle
+		    (ctx, DrawMode::fill(), self.drawn_position(c, i).mint(), r, 0.5,
+		     graphics::Color::BLACK)?;
+		graphics::draw(ctx, &dot, (Point2::<f32>{x:0.0, y:0.0},))?;
+	    }
+	}
+
+	graphics::present(ctx)
+    }
+
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context,
+			       _button: MouseButton, x: f32, y: f32) {
+	let mouse = Point{ x:x as f64, y:y as f64};
+	for (c, chain) in self.sim.chains.iter_mut().enumerate() {
+	    for (i, particle) in chain.particles.iter_mut().enumerate() {
+		if particle.position.distance(mouse) < 15.0 {
+		    self.grab = Some((c, i, particle.inv_mass));
+		    // pinned while held, so the constraints can't pull it off the cursor
+		    particle.inv_mass = 0.0;
+		    return;
+		}
+	    }
+	}
+    }
+
+    fn mouse_button_up_event(&mut self, _ctx: &mut Context,
+			       _button: MouseButton, _x: f32, _y: f32) {
+	if let Some((c, i, inv_mass)) = self.grab.take() {
+	    self.sim.chains[c].particles[i].inv_mass = inv_mass;
+	}
+    }
+
+    fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32,
+			  _xrel: f32, _yrel: f32) {
+	if let Some((c, i, _)) = self.grab {
+	    self.sim.chains[c].particles[i].position = Point{ x:x as f64, y:y as f64};
+	}
+    }
+}
@@ -1,5 +1,4 @@
-mod geometry;
-use geometry::*;
+use stad_demos::geometry::*;
 
 fn main() {
     let v = Vector {x:3.0, y:4.0};
@@ -10,15 +9,16 @@ fn main() {
     let p3 = Point {x:1.0, y:1.0};
     
     let l1 = Line::from_point_point(p1, p2);
-    let lq = Line::from_point_point(p1.inverted(), p2.inverted()).inverted();
-    println!("{:?}  {:?}", l1, lq);
-    assert_eq!(l1.slope(), 2.0);
+    let pl1 = ParametricLine::from_point_point(p1, p2);
+    println!("{:?}  {:?}", l1, pl1.to_line());
+    assert!(l1.signed_distance(p2).abs() < 1e-12);
 
-    let l2 = Line::from_point_slope(p3, inverse(l1.slope()));
-    assert_eq!(l2.slope(), -0.5);
+    let l2 = l1.perpendicular_through(p3);
+    assert!(l2.direction().dot(pl1.dir).abs() < 1e-12);
 
-    println!("{:?}", l1.intersection(l2));
-    //assert_eq!(l1.intersection(&l2), Point{x:1.8, y:0.6});
+    println!("{:?}", l1.checked_intersection(l2));
+    let t = pl1.intersection_t(l2).unwrap();
+    assert!(pl1.at(t).distance(Point{x:1.8, y:0.6}) < 1e-12);
     
     println!("Done");
 }
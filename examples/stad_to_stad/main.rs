@@ -1,12 +1,12 @@
 use ggez::{Context, ContextBuilder, GameResult, conf};
 use ggez::event::{self, EventHandler};
 use ggez::graphics;
-use ggez::graphics::{DrawMode};//, FillOptions};
 use ggez::input::mouse::MouseButton;
-use ggez::mint::Point2;
 
-mod geometry;
-use geometry::*;
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::world::*;
+use stad_demos::demo_scenes;
 
 /*
     fn get_line(&self) -> Line {
@@ -69,38 +69,11 @@ use geometry::*;
 static WINDOW_WIDTH : f64 = 1200.0;
 static WINDOW_HEIGHT: f64 = 800.0;
 
-fn visible_segment_from_line(line: Line) -> Option<Segment> {
-    // The line (if it is visible) will pass through two borders of the window 
-    // so we can use the two points where th line intersects the edges of the
-    // window
-    let mut v = vec![];
-    if 0.0 <= line.x(0.0) && line.x(0.0) <= WINDOW_WIDTH  {
-	v.push(Point{x:line.x(0.0), y:0.0});
-    }
-    if 0.0 < line.x(WINDOW_HEIGHT) && line.x(WINDOW_HEIGHT) < WINDOW_WIDTH {
-	v.push(Point{x:line.x(WINDOW_HEIGHT), y:WINDOW_HEIGHT});
-    }
-    if 0.0 <= line.y(0.0) && line.y(0.0) <= WINDOW_HEIGHT {
-	v.push(Point{x:0.0, y:line.y(0.0)});
-    }
-    if 0.0 < line.y(WINDOW_WIDTH) && line.y(WINDOW_WIDTH) < WINDOW_HEIGHT {
-	v.push(Point{x:WINDOW_WIDTH, y:line.y(WINDOW_WIDTH)});
-    }
-    if v.len() == 2 {
-	Some(Segment {
-	    p1: v[0],
-	    p2: v[1],
-	})
-    } else {
-	None
-    }
-}
-
 
 struct MyGame {
-    stad_a: Stad,
-    stad_b: Stad,
-    my_color: graphics::Color,
+    world: World,
+    stad_a: Handle,
+    stad_b: Handle,
     dragging: Option<usize>,
 }
 
@@ -146,13 +119,34 @@ fn main() -> Result<(), ggez::GameError> {
 
 impl MyGame {
     pub fn new(_ctx: &mut Context) -> MyGame {
+	let mut world = World::new();
+	let stad_a = world.add_stad(Stad::new(100.0,100.0, 200.0,100.0, 40.0));
+	let stad_b = world.add_stad(Stad::new(100.0,300.0, 300.0,300.0, 120.0));
         MyGame {
-	    stad_a: Stad::new(100.0,100.0, 200.0,100.0, 40.0),
-	    stad_b: Stad::new(100.0,300.0, 300.0,300.0, 120.0),
-	    my_color: graphics::Color::new(0.05, 0.7, 0.25, 0.8),
+	    world,
+	    stad_a,
+	    stad_b,
 	    dragging: None,
 	}
     }
+
+    fn stad(&self, h: Handle) -> Stad {
+	match self.world.get(h).map(|c| c.shape) {
+	    Some(Shape::Stad(s)) => s,
+	    _ => panic!("the demo only holds stadiums"),
+	}
+    }
+
+    // endpoints are numbered a.p1, a.p2, b.p1, b.p2
+    fn move_endpoint(&mut self, n: usize, p: Point) {
+	let h = if n < 2 { self.stad_a } else { self.stad_b };
+	let mut s = self.stad(h);
+	match n {
+	    0 | 2 => s.p1 = p,
+	    _     => s.p2 = p,
+	}
+	self.world.set_shape(h, Shape::Stad(s));
+    }
 }
 
 
@@ -163,136 +157,18 @@ impl EventHandler<ggez::GameError> for MyGame {
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
-        macro_rules! ezdraw{
-            ($a:expr)=>{
-                {
-                    graphics::draw(ctx, & $a, (Point2::<f32>{x:0.0, y:0.0},))?
-                }
-            }
-        }
-
-        // Clear
-        graphics::clear(ctx, graphics::Color::WHITE);
-
-	// Change color for collision
-	if self.stad_a.collides_stad(self.stad_b) {
-	//if self.stad_b.segment().shadows_point(self.stad_a.p1) {
-	    self.my_color = graphics::Color::new(0.7, 0.45, 0.05, 0.8);
-	} else {
-	    self.my_color = graphics::Color::new(0.05, 0.7, 0.25, 0.8);
-	}
-
-	// Draw the stadiums
-	let my_stroke = match DrawMode::stroke((self.stad_a.r*2.0) as f32) {
-	    DrawMode::Stroke(so) => {
-		so.with_start_cap(graphics::LineCap::Round)
-		    .with_end_cap(graphics::LineCap::Round)
-	    },
-	    DrawMode::Fill(_) => panic!("unreachable"),
-	};
-	let my_stroke_mode = DrawMode::Stroke(my_stroke);
-	let gstad_a = graphics::Mesh::new_polyline(ctx, my_stroke_mode,
-                                                 &[self.stad_a.p1.mint(), self.stad_a.p2.mint()],
-                                                 self.my_color)?;
-	let my_stroke2 = match DrawMode::stroke((self.stad_b.r*2.0) as f32) {
-	    DrawMode::Stroke(so) => {
-		so.with_start_cap(graphics::LineCap::Round)
-		    .with_end_cap(graphics::LineCap::Round)
-	    },
-	    DrawMode::Fill(_) => panic!("unreachable"),
-	};
-	let my_stroke_mode2 = DrawMode::Stroke(my_stroke2);
-	let gstad_b = graphics::Mesh::new_polyline(ctx, my_stroke_mode2,
-                                                 &[self.stad_b.p1.mint(), self.stad_b.p2.mint()],
-                                                 self.my_color)?;
-	ezdraw!(gstad_a);
-	ezdraw!(gstad_b);
-
-
-	// draw a line
-	let yellow = graphics::Color::new(0.9, 0.7, 0.15, 0.7);
-	let aline = self.stad_a.line();
-	let aaa = visible_segment_from_line(aline).unwrap();
-	let gaaa = graphics::Mesh::new_line(ctx, &[aaa.p1.mint(), aaa.p2.mint()],
-					    2.0, yellow)?;
-	ezdraw!(gaaa);
-
-	/*
-	// Draw parallel extension
-        let gray = graphics::Color::new(0.5, 0.5, 0.5, 0.5);
-        let extline = self.stad.get_extended_parallel(ctx, gray, 6.0)?;
-        ezdraw!(extline);
-
-	// Draw distance lines
-	let yellow = graphics::Color::new(0.9, 0.7, 0.15, 0.7);
-	let dist_line1 = graphics::Mesh::new_line(ctx, &[self.circ.a, self.stad.a],
-                                                  8.0, yellow)?;
-	let dist_line2 = graphics::Mesh::new_line(ctx, &[self.circ.a, self.stad.b],
-						  8.0, yellow)?;
-	ezdraw!(dist_line1);
-	ezdraw!(dist_line2);	
-	*/
-	
-	// Draw Radii
-        /*
-	let purple = graphics::Color::new(0.5, 0.0, 0.8, 0.9);
-        let radline_to_a = self.circ.get_radial_line_towards
-            (ctx, self.stad.a, purple, 8.5)?;
-        let radline_to_b = self.circ.get_radial_line_towards
-            (ctx, self.stad.b, purple, 8.5)?;
-	ezdraw!(radline_to_a);
-	ezdraw!(radline_to_b);
-        let radline_from_a = self.stad.get_radial_line_a_towards
-            (ctx, self.circ.a, purple, 8.5)?;
-        let radline_from_b = self.stad.get_radial_line_b_towards
-            (ctx, self.circ.a, purple, 8.5)?;
-	ezdraw!(radline_from_a);        
-	ezdraw!(radline_from_b);
-
-
-	// Draw Perpendicularity
-	let orange = graphics::Color::new(0.9, 0.7, 0.15, 0.7);
-	let perpen = self.stad.get_perpendicular_segment
-	    (ctx, self.circ.a, orange, 6.0)?;
-        ezdraw!(perpen);
-	 */
-	
-	// Draw Perpendicular Radii
-	/*
-        let purple2 = graphics::Color::new(0.7, 0.1, 0.7, 0.9);
-	let perp_rad_from_circ = self.circ.get_radial_line_towards_line
-	    (ctx, self.stad.get_line(), purple2, 6.0)?;
-	let perp_rad_from_stad = self.stad.get_perpendicular_radius
-	    (ctx, self.circ.a, purple2, 6.0)?;
-	ezdraw!(perp_rad_from_circ);
-	ezdraw!(perp_rad_from_stad);
-	 */
-	
-	// Draw selector indicators
-        let a1_selec = graphics::Mesh::new_circle
-            (ctx, DrawMode::stroke(4.0), self.stad_a.p1.mint(), 15.0, 1.0,
-             graphics::Color::BLACK)?;
-        let a2_selec = graphics::Mesh::new_circle
-            (ctx, DrawMode::stroke(4.0), self.stad_a.p2.mint(), 15.0, 1.0,
-             graphics::Color::BLACK)?;
-        let b1_selec = graphics::Mesh::new_circle
-            (ctx, DrawMode::stroke(4.0), self.stad_b.p1.mint(), 15.0, 1.0,
-             graphics::Color::BLACK)?;
-        let b2_selec = graphics::Mesh::new_circle
-            (ctx, DrawMode::stroke(4.0), self.stad_b.p2.mint(), 15.0, 1.0,
-             graphics::Color::BLACK)?;
-        ezdraw!(a1_selec);
-        ezdraw!(a2_selec);
-        ezdraw!(b1_selec);
-        ezdraw!(b2_selec);
-        
+	let colliding = !self.world.colliding_pairs().is_empty();
+	let scene = demo_scenes::stad_to_stad(self.stad(self.stad_a), self.stad(self.stad_b),
+					       colliding, WINDOW_WIDTH, WINDOW_HEIGHT);
+	scene.draw(ctx)?;
         graphics::present(ctx)
     }
 
 
     fn mouse_button_down_event(&mut self, _ctx: &mut Context,
 			       _button: MouseButton, x: f32, y: f32) {
-	for (n, p) in [self.stad_a.p1, self.stad_a.p2, self.stad_b.p1, self.stad_b.p2].iter().enumerate() {
+	let (stad_a, stad_b) = (self.stad(self.stad_a), self.stad(self.stad_b));
+	for (n, p) in [stad_a.p1, stad_a.p2, stad_b.p1, stad_b.p2].iter().enumerate() {
 	    if p.distance(Point{ x:x as f64, y:y as f64}) < 15.0 {
 	        self.dragging = Some(n);
 	        return;
@@ -307,25 +183,9 @@ impl EventHandler<ggez::GameError> for MyGame {
 
     fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32,
 			  _xrel: f32, _yrel: f32) {
-        match self.dragging {
-	    Some(0) => {
-	        self.stad_a.p1.x = x as f64;
-	        self.stad_a.p1.y = y as f64;		
-            },
-	    Some(1) => {
-	        self.stad_a.p2.x = x as f64;
-	        self.stad_a.p2.y = y as f64;		
-            },
-	    Some(2) => {
-	        self.stad_b.p1.x = x as f64;
-	        self.stad_b.p1.y = y as f64;		
-            },
-	    Some(3) => {
-	        self.stad_b.p2.x = x as f64;
-	        self.stad_b.p2.y = y as f64;		
-            },
-	    _ => (),
-        };
+        if let Some(n) = self.dragging {
+	    self.move_endpoint(n, Point{ x:x as f64, y:y as f64});
+        }
     }
     
 }
@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+
+// Past this size squared distances, and the products the closest point math
+// takes of them, overflow to infinity, so this is as far out as the "no NaN"
+// checks go. Anything at all still mustn't panic.
+pub const LIMIT: f64 = 1e50;
+
+pub fn sane(values: &[f64]) -> bool {
+    values.iter().all(|v| v.is_finite() && v.abs() <= LIMIT)
+}
+
+pub fn point(v: [f64; 2]) -> Point {
+    Point { x: v[0], y: v[1] }
+}
+
+pub fn vector(v: [f64; 2]) -> Vector {
+    Vector { x: v[0], y: v[1] }
+}
+
+pub fn segment(v: [f64; 4]) -> Segment {
+    Segment { p1: Point { x: v[0], y: v[1] }, p2: Point { x: v[2], y: v[3] } }
+}
+
+// the first value picks circle or stad
+pub fn shape(v: [f64; 6]) -> Shape {
+    if v[0].is_sign_negative() {
+	Shape::Circle(Circle { p: Point { x: v[1], y: v[2] }, r: v[5] })
+    } else {
+	Shape::Stad(Stad {
+	    p1: Point { x: v[1], y: v[2] },
+	    p2: Point { x: v[3], y: v[4] },
+	    r: v[5],
+	})
+    }
+}
+
+// the numbers that describe a shape (not the selector)
+pub fn shape_values(v: [f64; 6]) -> Vec<f64> {
+    v[1..].to_vec()
+}
+
+pub fn check(what: &str, v: f64) {
+    assert!(!v.is_nan(), "{} is NaN", what);
+}
+
+pub fn check_point(what: &str, p: Point) {
+    assert!(!p.x.is_nan() && !p.y.is_nan(), "{} is NaN: {:?}", what, p);
+}
+
+pub fn check_vector(what: &str, v: Vector) {
+    assert!(!v.x.is_nan() && !v.y.is_nan(), "{} is NaN: {:?}", what, v);
+}
@@ -0,0 +1,66 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use arbitrary::Arbitrary;
+
+use stad_demos::geometry::*;
+
+mod common;
+use common::*;
+
+#[derive(Debug)]
+#[derive(Arbitrary)]
+struct Input {
+    a: [f64; 4],
+    b: [f64; 4],
+    p: [f64; 2],
+    v: [f64; 2],
+}
+
+fuzz_target!(|input: Input| {
+    let p = point(input.p);
+    let v = vector(input.v);
+    // the unchecked constructors are allowed to give NaN lines for equal
+    // points; they just mustn't panic
+    let _ = Line::from_point_point(point([input.a[0], input.a[1]]), point([input.a[2], input.a[3]]));
+    let line = Line::checked_from_point_point(point([input.a[0], input.a[1]]), point([input.a[2], input.a[3]]));
+    let other = Line::checked_from_point_point(point([input.b[0], input.b[1]]), point([input.b[2], input.b[3]]));
+    let (line, other) = match (line, other) {
+	(Ok(line), Ok(other)) => (line, other),
+	_ => return,
+    };
+
+    let signed = line.signed_distance(p);
+    let distance = line.distance_to_point(p);
+    let projected = line.project(p);
+    let reflected = line.reflect(p);
+    let bounced = line.reflect_vector(v);
+    let perpendicular = line.perpendicular_through(p);
+    let crossing = line.checked_intersection(other);
+    let parametric = line.to_parametric();
+    let t = parametric.closest_t(p);
+    let t_other = parametric.intersection_t(other);
+
+    let mut values = input.a.to_vec();
+    values.extend_from_slice(&input.b);
+    values.extend_from_slice(&input.p);
+    values.extend_from_slice(&input.v);
+    if !sane(&values) {
+	return;
+    }
+    check("signed_distance", signed);
+    check("distance_to_point", distance);
+    check_point("project", projected);
+    check_point("reflect", reflected);
+    check_vector("reflect_vector", bounced);
+    check("perpendicular_through", perpendicular.signed_distance(p));
+    check_vector("normal", line.normal());
+    check_point("to_parametric", parametric.origin);
+    check("closest_t", t);
+    if let Some(t) = t_other {
+	check("intersection_t", t);
+    }
+    if let Ok(q) = crossing {
+	check_point("checked_intersection", q);
+    }
+    check("to_line", parametric.to_line().signed_distance(p));
+});
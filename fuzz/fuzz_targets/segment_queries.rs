@@ -0,0 +1,49 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use arbitrary::Arbitrary;
+
+mod common;
+use common::*;
+
+#[derive(Debug)]
+#[derive(Arbitrary)]
+struct Input {
+    a: [f64; 4],
+    b: [f64; 4],
+    p: [f64; 2],
+}
+
+fuzz_target!(|input: Input| {
+    let (a, b, p) = (segment(input.a), segment(input.b), point(input.p));
+
+    let shadows = a.shadows_point(p);
+    let distance = a.distance_to_point(p);
+    let closest = a.closest_point(p);
+    let (ca, cb) = a.closest_points(b);
+    let between = a.distance_to_segment(b);
+    let overlap = a.is_overlap(b);
+    let near = a.near_point_along(p);
+
+    let mut values = input.a.to_vec();
+    values.extend_from_slice(&input.b);
+    values.extend_from_slice(&input.p);
+    if !sane(&values) {
+	return;
+    }
+    check("distance_to_point", distance);
+    check_point("closest_point", closest);
+    check_point("closest_points", ca);
+    check_point("closest_points", cb);
+    check("distance_to_segment", between);
+    check_point("near_point_along", near);
+    if overlap {
+	assert!(between == 0.0, "overlapping segments {} apart", between);
+    }
+    if shadows {
+	assert!(a.p1 != a.p2);
+    }
+    if a.p1 != a.p2 {
+	let perpendicular = a.perpendicular_through(p);
+	check("perpendicular_through", perpendicular.signed_distance(p));
+    }
+});
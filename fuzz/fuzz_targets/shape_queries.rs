@@ -0,0 +1,59 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use arbitrary::Arbitrary;
+
+use stad_demos::query::*;
+
+mod common;
+use common::*;
+
+#[derive(Debug)]
+#[derive(Arbitrary)]
+struct Input {
+    a: [f64; 6],
+    b: [f64; 6],
+    p: [f64; 2],
+    motion: [f64; 2],
+}
+
+fuzz_target!(|input: Input| {
+    let (a, b) = (shape(input.a), shape(input.b));
+    let p = point(input.p);
+    let motion = vector(input.motion);
+    let ray = Ray { origin: p, dir: motion };
+
+    let collides = a.collides(b);
+    let gap = a.gap(b);
+    let contact = a.contact(b);
+    let _ = a.collides_certified(b);
+    let _ = a.contains_point(p);
+    let hit = a.raycast(ray);
+    let (separation, pa, pb) = a.separation(b);
+    let swept = a.sweep(motion, b);
+
+    let mut values = shape_values(input.a);
+    values.extend(shape_values(input.b));
+    values.extend_from_slice(&input.p);
+    values.extend_from_slice(&input.motion);
+    if !sane(&values) {
+	return;
+    }
+    check("gap", gap);
+    check("separation", separation);
+    check_point("separation", pa);
+    check_point("separation", pb);
+    if let Some(c) = contact {
+	assert!(collides, "a contact without a collision");
+	check_vector("contact normal", c.normal);
+	check("contact depth", c.depth);
+	check_point("contact point", c.point);
+    }
+    if let Some(t) = hit {
+	check("raycast", t);
+    }
+    if let Some(h) = swept {
+	check("sweep t", h.t);
+	check_vector("sweep normal", h.normal);
+	check_point("sweep point", h.point);
+    }
+});
@@ -0,0 +1,330 @@
+use std::f64::consts::PI;
+
+use crate::geometry::*;
+use crate::collider::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Area properties
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// `second_moment` is the polar second moment of area about the centroid
+// (the integral of r^2 over the shape), which times a density is the moment
+// of inertia a rigid body uses.
+impl Circle {
+
+    pub fn area(&self) -> f64 {
+	PI * self.r * self.r
+    }
+
+    pub fn perimeter(&self) -> f64 {
+	2.0 * PI * self.r
+    }
+
+    pub fn centroid(&self) -> Point {
+	self.p
+    }
+
+    pub fn second_moment(&self) -> f64 {
+	self.area() * self.r * self.r / 2.0
+    }
+}
+
+impl Stad {
+
+    pub fn area(&self) -> f64 {
+	2.0 * self.r * self.p1.distance(self.p2) + PI * self.r * self.r
+    }
+
+    pub fn perimeter(&self) -> f64 {
+	2.0 * self.p1.distance(self.p2) + 2.0 * PI * self.r
+    }
+
+    // symmetric, so just the middle
+    pub fn centroid(&self) -> Point {
+	self.p1.lerp(self.p2, 0.5)
+    }
+
+    // A stadium is a 2r by L rectangle with a half disc on each end. Each half
+    // disc's centroid sits 4r/3pi past the end of the rectangle, which with the
+    // parallel axis theorem gives its moment about the stadium's center.
+    pub fn second_moment(&self) -> f64 {
+	let len = self.p1.distance(self.p2);
+	let r = self.r;
+
+	let rect_area = len * 2.0 * r;
+	let rect_moment = rect_area * (len*len + 4.0*r*r) / 12.0;
+
+	let half_area = PI * r * r / 2.0;
+	let offset = 4.0 * r / (3.0 * PI);
+	let arm = len/2.0 + offset;
+	let half_moment = half_area*r*r/2.0 - half_area*offset*offset + half_area*arm*arm;
+
+	rect_moment + 2.0*half_moment
+    }
+}
+
+impl Polygon {
+
+    pub fn area(&self) -> f64 {
+	self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> f64 {
+	self.edges().map(|e| e.p1.distance(e.p2)).sum()
+    }
+
+    // The usual sums over the triangles each edge makes with a reference
+    // point. The first point is used as that reference rather than the origin
+    // so polygons far from the origin don't lose precision. A polygon with no
+    // area has no centroid, so that's an error rather than a NaN.
+    pub fn centroid(&self) -> Result<Point, GeometryError> {
+	if self.points.len() < 3 {
+	    return Err(GeometryError::DegeneratePolygon);
+	}
+	let o = self.points[0];
+	let (mut twice_area, mut cx, mut cy) = (0.0, 0.0, 0.0);
+	for e in self.edges() {
+	    let (a, b) = (o.towards(e.p1), o.towards(e.p2));
+	    let cross = a.cross(b);
+	    twice_area += cross;
+	    cx += (a.x + b.x) * cross;
+	    cy += (a.y + b.y) * cross;
+	}
+	if twice_area == 0.0 {
+	    return Err(GeometryError::DegeneratePolygon);
+	}
+	Ok(o.plus(Vector { x: cx, y: cy }.scaled(1.0 / (3.0 * twice_area))))
+    }
+
+    // about the first point, then moved to the centroid with the parallel
+    // axis theorem
+    pub fn second_moment(&self) -> Result<f64, GeometryError> {
+	let centroid = self.centroid()?;
+	let o = self.points[0];
+	let mut moment = 0.0;
+	for e in self.edges() {
+	    let (a, b) = (o.towards(e.p1), o.towards(e.p2));
+	    moment += a.cross(b) * (a.dot(a) + a.dot(b) + b.dot(b));
+	}
+	let c = o.towards(centroid);
+	Ok((moment / 12.0).abs() - self.area() * c.dot(c))
+    }
+}
+
+impl Shape {
+
+    pub fn area(&self) -> f64 {
+	match self {
+	    Shape::Circle(c) => c.area(),
+	    Shape::Stad(s)   => s.area(),
+	}
+    }
+
+    pub fn perimeter(&self) -> f64 {
+	match self {
+	    Shape::Circle(c) => c.perimeter(),
+	    Shape::Stad(s)   => s.perimeter(),
+	}
+    }
+
+    pub fn centroid(&self) -> Point {
+	match self {
+	    Shape::Circle(c) => c.centroid(),
+	    Shape::Stad(s)   => s.centroid(),
+	}
+    }
+
+    pub fn second_moment(&self) -> f64 {
+	match self {
+	    Shape::Circle(c) => c.second_moment(),
+	    Shape::Stad(s)   => s.second_moment(),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Intersection area
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// For the circle and stadium, Green's theorem: the area of a region is half
+// the integral of (x dy - y dx) anticlockwise round its edge, and the edge of
+// the overlap is the parts of the stadium's edge inside the circle plus the
+// parts of the circle's edge inside the stadium. Each edge piece (a straight
+// side or an arc) is cut where it crosses the other shape's edge, and the
+// bits whose middles are inside count. Both pieces have closed form
+// integrals, so the answer is exact up to rounding.
+
+// the t in [0, 1] where a + t(b - a) is on the circle
+fn segment_crossings(a: Point, b: Point, c: Circle) -> Vec<f64> {
+    let d = a.towards(b);
+    let m = c.p.towards(a);
+    let (qa, qb, qc) = (d.dot(d), m.dot(d), m.dot(m) - c.r*c.r);
+    let disc = qb*qb - qa*qc;
+    if qa == 0.0 || disc < 0.0 {
+	return vec![];
+    }
+    let root = disc.sqrt();
+    [(-qb - root)/qa, (-qb + root)/qa].iter().cloned()
+	.filter(|t| (0.0..=1.0).contains(t))
+	.collect()
+}
+
+// where the edges of two circles cross
+fn circle_crossings(a: Circle, b: Circle) -> Vec<Point> {
+    let d = a.p.distance(b.p);
+    if d == 0.0 || d > a.r + b.r || d < (a.r - b.r).abs() {
+	return vec![];
+    }
+    // distance from a's center to the chord, and half the chord
+    let along = (d*d + a.r*a.r - b.r*b.r) / (2.0*d);
+    let half = (a.r*a.r - along*along).max(0.0).sqrt();
+    let u = a.p.towards(b.p).normalized();
+    let mid = a.p.plus(u.scaled(along));
+    vec![mid.plus(u.perpendicular().scaled(half)), mid.minus(u.perpendicular().scaled(half))]
+}
+
+fn angle_of(center: Point, p: Point) -> f64 {
+    let v = center.towards(p);
+    v.y.atan2(v.x)
+}
+
+// theta moved by whole turns into [from, from + 2pi)
+fn wrap_from(theta: f64, from: f64) -> f64 {
+    from + (theta - from).rem_euclid(2.0*PI)
+}
+
+// x dy - y dx along the straight line from a to b
+fn segment_integral(a: Point, b: Point) -> f64 {
+    a.x*b.y - a.y*b.x
+}
+
+// x dy - y dx anticlockwise round the circle from angle t0 to t1
+fn arc_integral(c: Circle, t0: f64, t1: f64) -> f64 {
+    c.r*c.r*(t1 - t0) + c.r*(c.p.x*(t1.sin() - t0.sin()) - c.p.y*(t1.cos() - t0.cos()))
+}
+
+// the edge of a stadium, anticlockwise, as two sides and two end caps
+enum EdgePiece {
+    Side(Point, Point),
+    Cap(Circle, f64, f64),
+}
+
+fn stad_edge(s: Stad) -> Vec<EdgePiece> {
+    let u = s.p1.towards(s.p2).normalized();
+    let n = u.perpendicular().scaled(s.r);
+    let phi = u.y.atan2(u.x);
+    vec![
+	EdgePiece::Side(s.p1.minus(n), s.p2.minus(n)),
+	EdgePiece::Cap(Circle { p: s.p2, r: s.r }, phi - PI/2.0, phi + PI/2.0),
+	EdgePiece::Side(s.p2.plus(n), s.p1.plus(n)),
+	EdgePiece::Cap(Circle { p: s.p1, r: s.r }, phi + PI/2.0, phi + 3.0*PI/2.0),
+    ]
+}
+
+impl Circle {
+
+    pub fn intersection_area_circle(&self, other: Circle) -> f64 {
+	let d = self.p.distance(other.p);
+	let (r1, r2) = (self.r, other.r);
+	if d >= r1 + r2 {
+	    return 0.0;
+	}
+	if d <= (r1 - r2).abs() {
+	    let r = r1.min(r2);
+	    return PI * r * r;
+	}
+	// two circular segments either side of the common chord
+	let segment = |r: f64, along: f64| r*r*(along/r).clamp(-1.0, 1.0).acos() - along*(r*r - along*along).max(0.0).sqrt();
+	let along1 = (d*d + r1*r1 - r2*r2) / (2.0*d);
+	segment(r1, along1) + segment(r2, d - along1)
+    }
+
+    pub fn intersection_area_stad(&self, other: Stad) -> f64 {
+	if self.r <= 0.0 || other.r <= 0.0 {
+	    return 0.0;
+	}
+	if other.p1 == other.p2 {
+	    return self.intersection_area_circle(Circle { p: other.p1, r: other.r });
+	}
+	// One inside the other. This also covers the circle being exactly one of
+	// the end caps, where the two edges run along each other and splitting
+	// them up wouldn't work.
+	if other.segment().closest_point(self.p).distance(self.p) + self.r <= other.r {
+	    return self.area();
+	}
+	if self.p.distance(other.p1).max(self.p.distance(other.p2)) + other.r <= self.r {
+	    return other.area();
+	}
+	// with the circle at the origin, so the integrals stay small
+	let circle = Circle { p: Point { x: 0.0, y: 0.0 }, r: self.r };
+	let stad = other.translated(self.p.towards(circle.p));
+	let inside_circle = |p: Point| p.distance(circle.p) < circle.r;
+	let inside_stad = |p: Point| stad.segment().closest_point(p).distance(p) < stad.r;
+
+	let mut total = 0.0;
+	// angles round the circle where the stadium's edge crosses it
+	let mut crossings = vec![];
+	for piece in stad_edge(stad) {
+	    match piece {
+		EdgePiece::Side(a, b) => {
+		    let mut cuts = segment_crossings(a, b, circle);
+		    crossings.extend(cuts.iter().map(|&t| angle_of(circle.p, a.lerp(b, t))));
+		    cuts.extend_from_slice(&[0.0, 1.0]);
+		    cuts.sort_by(|x, y| x.total_cmp(y));
+		    for w in cuts.windows(2) {
+			if inside_circle(a.lerp(b, (w[0] + w[1]) / 2.0)) {
+			    total += segment_integral(a.lerp(b, w[0]), a.lerp(b, w[1]));
+			}
+		    }
+		},
+		EdgePiece::Cap(cap, t0, t1) => {
+		    let mut cuts = vec![t0, t1];
+		    for p in circle_crossings(cap, circle) {
+			let t = wrap_from(angle_of(cap.p, p), t0);
+			if t <= t1 {
+			    cuts.push(t);
+			    crossings.push(angle_of(circle.p, p));
+			}
+		    }
+		    cuts.sort_by(|x, y| x.total_cmp(y));
+		    for w in cuts.windows(2) {
+			let mid = (w[0] + w[1]) / 2.0;
+			let p = cap.p.plus(Vector { x: mid.cos(), y: mid.sin() }.scaled(cap.r));
+			if inside_circle(p) {
+			    total += arc_integral(cap, w[0], w[1]);
+			}
+		    }
+		},
+	    }
+	}
+
+	// then round the circle itself, between the crossings
+	let on_circle = |t: f64| Point { x: t.cos() * circle.r, y: t.sin() * circle.r };
+	if crossings.is_empty() {
+	    if inside_stad(on_circle(0.0)) {
+		total += arc_integral(circle, 0.0, 2.0*PI);
+	    }
+	} else {
+	    let start = crossings[0];
+	    let mut cuts: Vec<f64> = crossings.iter().map(|&t| wrap_from(t, start)).collect();
+	    cuts.push(start + 2.0*PI);
+	    cuts.sort_by(|x, y| x.total_cmp(y));
+	    for w in cuts.windows(2) {
+		if inside_stad(on_circle((w[0] + w[1]) / 2.0)) {
+		    total += arc_integral(circle, w[0], w[1]);
+		}
+	    }
+	}
+	(total / 2.0).max(0.0)
+    }
+}
+
+impl Stad {
+
+    pub fn intersection_area_circle(&self, other: Circle) -> f64 {
+	other.intersection_area_stad(*self)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
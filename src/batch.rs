@@ -0,0 +1,40 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::contact::*;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Every pair is evaluated on its own with no shared state so the results are
+// the same (and in the same order) no matter how many threads rayon uses, or
+// whether the `parallel` feature is on at all.
+#[cfg(feature = "parallel")]
+fn map_pairs<T, R, F>(pairs: &[T], f: F) -> Vec<R>
+where T: Sync, R: Send, F: Fn(&T) -> R + Sync + Send {
+    pairs.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn map_pairs<T, R, F>(pairs: &[T], f: F) -> Vec<R>
+where F: Fn(&T) -> R {
+    pairs.iter().map(f).collect()
+}
+
+pub fn collides_stad_batch(pairs: &[(Stad, Stad)]) -> Vec<bool> {
+    map_pairs(pairs, |(a, b)| a.collides_stad(*b))
+}
+
+pub fn contact_stad_batch(pairs: &[(Stad, Stad)]) -> Vec<Option<Contact>> {
+    map_pairs(pairs, |(a, b)| a.contact_stad(*b))
+}
+
+pub fn contact_batch(pairs: &[(Shape, Shape)]) -> Vec<Option<Contact>> {
+    map_pairs(pairs, |(a, b)| a.contact(*b))
+}
+
+// Narrow phase for index pairs, typically straight out of
+// `broadphase::candidate_pairs`. Filters are honored.
+pub fn collider_contact_batch(colliders: &[Collider], pairs: &[(usize, usize)])
+			      -> Vec<Option<Contact>> {
+    map_pairs(pairs, |&(i, j)| colliders[i].contact(&colliders[j]))
+}
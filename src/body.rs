@@ -0,0 +1,160 @@
+use crate::geometry::*;
+use crate::collider::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Mass properties
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// mass and moment of inertia about the centroid for a uniform density
+pub struct MassProperties {
+    pub mass: f64,
+    pub inertia: f64,
+}
+
+// the area and second moment (see `area`) times the density
+impl Circle {
+    pub fn mass_properties(&self, density: f64) -> MassProperties {
+	MassProperties {
+	    mass: density * self.area(),
+	    inertia: density * self.second_moment(),
+	}
+    }
+}
+
+impl Stad {
+    pub fn mass_properties(&self, density: f64) -> MassProperties {
+	MassProperties {
+	    mass: density * self.area(),
+	    inertia: density * self.second_moment(),
+	}
+    }
+}
+
+impl Shape {
+    pub fn mass_properties(&self, density: f64) -> MassProperties {
+	match self {
+	    Shape::Circle(c) => c.mass_properties(density),
+	    Shape::Stad(s)   => s.mass_properties(density),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// RigidBody
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// `local` is the collider with its centroid at the origin, as it sits when
+// `angle` is zero. Fixed bodies (walls, floors) have zero inverse mass and
+// inertia, so nothing can move them.
+pub struct RigidBody {
+    pub local: Collider,
+    pub position: Point,
+    pub angle: f64,
+    pub velocity: Vector,
+    pub angular_velocity: f64,
+    pub inv_mass: f64,
+    pub inv_inertia: f64,
+    pub restitution: f64,
+    pub friction: f64,
+    // sleeping bodies are skipped by the simulation until something wakes them
+    pub asleep: bool,
+    // how long the body has been slow enough to sleep
+    pub sleep_time: f64,
+}
+impl RigidBody {
+
+    // a body that starts out exactly where `collider` is
+    pub fn new(collider: Collider, density: f64) -> RigidBody {
+	let center = collider.shape.centroid();
+	let to_origin = center.towards(Point{x: 0.0, y: 0.0});
+	let props = collider.shape.mass_properties(density);
+	RigidBody {
+	    local: Collider { shape: collider.shape.translated(to_origin), ..collider },
+	    position: center,
+	    angle: 0.0,
+	    velocity: Vector{x: 0.0, y: 0.0},
+	    angular_velocity: 0.0,
+	    inv_mass: if props.mass > 0.0 { 1.0/props.mass } else { 0.0 },
+	    inv_inertia: if props.inertia > 0.0 { 1.0/props.inertia } else { 0.0 },
+	    restitution: 0.3,
+	    friction: 0.5,
+	    asleep: false,
+	    sleep_time: 0.0,
+	}
+    }
+
+    pub fn fixed(collider: Collider) -> RigidBody {
+	RigidBody {
+	    inv_mass: 0.0,
+	    inv_inertia: 0.0,
+	    ..RigidBody::new(collider, 1.0)
+	}
+    }
+
+    pub fn is_fixed(&self) -> bool {
+	self.inv_mass == 0.0 && self.inv_inertia == 0.0
+    }
+
+    // moving bodies that aren't asleep
+    pub fn is_awake(&self) -> bool {
+	!self.is_fixed() && !self.asleep
+    }
+
+    pub fn wake(&mut self) {
+	self.asleep = false;
+	self.sleep_time = 0.0;
+    }
+
+    // a point given relative to the body's centroid at angle zero, in world space
+    pub fn to_world(&self, local: Point) -> Point {
+	let v = Vector{x: local.x, y: local.y}.rotated(self.angle);
+	self.position.plus(v)
+    }
+
+    // inverse of to_world
+    pub fn to_local(&self, world: Point) -> Point {
+	let v = self.position.towards(world).rotated(-self.angle);
+	Point{x: v.x, y: v.y}
+    }
+
+    pub fn shape(&self) -> Shape {
+	match self.local.shape {
+	    Shape::Circle(c) => Shape::Circle(Circle {
+		p: self.to_world(c.p),
+		r: c.r,
+	    }),
+	    Shape::Stad(s) => Shape::Stad(Stad {
+		p1: self.to_world(s.p1),
+		p2: self.to_world(s.p2),
+		r: s.r,
+	    }),
+	}
+    }
+
+    pub fn collider(&self) -> Collider {
+	Collider { shape: self.shape(), ..self.local }
+    }
+
+    // velocity of the (world space) point p if it were attached to the body
+    pub fn velocity_at(&self, p: Point) -> Vector {
+	let r = self.position.towards(p);
+	self.velocity.plus(r.perpendicular().scaled(self.angular_velocity))
+    }
+
+    // impulse applied at world space point p
+    pub fn apply_impulse(&mut self, impulse: Vector, p: Point) {
+	let r = self.position.towards(p);
+	self.velocity = self.velocity.plus(impulse.scaled(self.inv_mass));
+	self.angular_velocity += r.cross(impulse) * self.inv_inertia;
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
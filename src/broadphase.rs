@@ -0,0 +1,162 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::tolerance::Tolerance;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Aabb
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+pub struct Aabb {
+    pub min: Point,
+    pub max: Point,
+}
+impl Aabb {
+
+    pub fn overlaps(&self, other: Aabb) -> bool {
+	self.min.x <= other.max.x && other.min.x <= self.max.x &&
+	    self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    pub fn contains_point(&self, p: Point) -> bool {
+	self.min.x <= p.x && p.x <= self.max.x &&
+	    self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    // true if other lies entirely inside self
+    pub fn contains(&self, other: Aabb) -> bool {
+	self.min.x <= other.min.x && other.max.x <= self.max.x &&
+	    self.min.y <= other.min.y && other.max.y <= self.max.y
+    }
+
+    pub fn union(&self, other: Aabb) -> Aabb {
+	Aabb {
+	    min: Point{x: self.min.x.min(other.min.x), y: self.min.y.min(other.min.y)},
+	    max: Point{x: self.max.x.max(other.max.x), y: self.max.y.max(other.max.y)},
+	}
+    }
+
+    // quadrants numbered 0: min x min y, 1: max x min y, 2: min x max y, 3: max x max y
+    pub fn quadrant(&self, q: usize) -> Aabb {
+	let mid = Point{x: (self.min.x + self.max.x)/2.0, y: (self.min.y + self.max.y)/2.0};
+	let (x0, x1) = if q & 1 == 0 { (self.min.x, mid.x) } else { (mid.x, self.max.x) };
+	let (y0, y1) = if q & 2 == 0 { (self.min.y, mid.y) } else { (mid.y, self.max.y) };
+	Aabb {
+	    min: Point{x: x0, y: y0},
+	    max: Point{x: x1, y: y1},
+	}
+    }
+}
+
+// The boxes are padded by the tolerance so that pairs which only count as
+// touching (see tolerance.rs) aren't culled before the narrow phase. Plain
+// `aabb` pads by the default one.
+impl Circle {
+    pub fn aabb(&self) -> Aabb {
+	self.aabb_with(Tolerance::default())
+    }
+
+    pub fn aabb_with(&self, tolerance: Tolerance) -> Aabb {
+	let r = self.r + tolerance.epsilon;
+	Aabb {
+	    min: Point{x: self.p.x - r, y: self.p.y - r},
+	    max: Point{x: self.p.x + r, y: self.p.y + r},
+	}
+    }
+}
+
+impl Stad {
+    pub fn aabb(&self) -> Aabb {
+	self.aabb_with(Tolerance::default())
+    }
+
+    pub fn aabb_with(&self, tolerance: Tolerance) -> Aabb {
+	let r = self.r + tolerance.epsilon;
+	Aabb {
+	    min: Point{x: self.p1.x.min(self.p2.x) - r,
+		       y: self.p1.y.min(self.p2.y) - r},
+	    max: Point{x: self.p1.x.max(self.p2.x) + r,
+		       y: self.p1.y.max(self.p2.y) + r},
+	}
+    }
+}
+
+impl Shape {
+    pub fn aabb(&self) -> Aabb {
+	self.aabb_with(Tolerance::default())
+    }
+
+    pub fn aabb_with(&self, tolerance: Tolerance) -> Aabb {
+	match self {
+	    Shape::Circle(c) => c.aabb_with(tolerance),
+	    Shape::Stad(s)   => s.aabb_with(tolerance),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Sweep and prune
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+
+// Pairs (i, j), i < j, whose boxes overlap and whose filters allow them to
+// touch. Boxes are sorted by their left edge and swept along x so only boxes
+// that share some x range are ever compared. Pairs come out sorted.
+//
+// The `_with` versions take the tolerance the boxes are padded by and the
+// narrow phase uses; pass the same one on to any contacts worked out for
+// the pairs.
+pub fn candidate_pairs(colliders: &[Collider]) -> Vec<(usize, usize)> {
+    candidate_pairs_with(colliders, Tolerance::default())
+}
+
+pub fn candidate_pairs_with(colliders: &[Collider], tolerance: Tolerance) -> Vec<(usize, usize)> {
+    let boxes: Vec<Aabb> = colliders.iter().map(|c| c.shape.aabb_with(tolerance)).collect();
+    let mut order: Vec<usize> = (0..colliders.len()).collect();
+    order.sort_by(|&a, &b| boxes[a].min.x.total_cmp(&boxes[b].min.x));
+
+    let mut pairs = vec![];
+    for (n, &i) in order.iter().enumerate() {
+	for &j in order[n+1..].iter() {
+	    if boxes[j].min.x > boxes[i].max.x {
+		break;
+	    }
+	    if colliders[i].can_collide(&colliders[j]) && boxes[i].overlaps(boxes[j]) {
+		pairs.push((i.min(j), i.max(j)));
+	    }
+	}
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+// The candidate pairs that actually pass the narrow phase
+pub fn colliding_pairs(colliders: &[Collider]) -> Vec<(usize, usize)> {
+    colliding_pairs_with(colliders, Tolerance::default())
+}
+
+pub fn colliding_pairs_with(colliders: &[Collider], tolerance: Tolerance) -> Vec<(usize, usize)> {
+    candidate_pairs_with(colliders, tolerance).into_iter()
+	.filter(|&(i, j)| colliders[i].shape.collides_with(colliders[j].shape, tolerance))
+	.collect()
+}
+
+// Indices of every collider that `probe` is allowed to touch and does touch
+pub fn overlapping(colliders: &[Collider], probe: &Collider) -> Vec<usize> {
+    overlapping_with(colliders, probe, Tolerance::default())
+}
+
+pub fn overlapping_with(colliders: &[Collider], probe: &Collider, tolerance: Tolerance) -> Vec<usize> {
+    let probe_box = probe.shape.aabb_with(tolerance);
+    colliders.iter().enumerate()
+	.filter(|(_, c)| probe.can_collide(c) && probe_box.overlaps(c.shape.aabb_with(tolerance)))
+	.filter(|(_, c)| probe.shape.collides_with(c.shape, tolerance))
+	.map(|(n, _)| n)
+	.collect()
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,177 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::static_geometry::StaticGeometry;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// CharacterSettings
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct CharacterSettings {
+    // the gap kept between the character and anything it slides along
+    pub skin: f64,
+    // steepest walkable slope, in radians from flat
+    pub max_slope: f64,
+    // tallest ledge the character will walk up onto without jumping
+    pub step_height: f64,
+    // how far below the feet to look for ground
+    pub ground_probe: f64,
+    pub max_slides: usize,
+    // unit vector pointing up; (0, -1) on screen
+    pub up: Vector,
+}
+
+impl Default for CharacterSettings {
+    fn default() -> CharacterSettings {
+	CharacterSettings {
+	    skin: 0.5,
+	    max_slope: 50f64.to_radians(),
+	    step_height: 16.0,
+	    ground_probe: 2.0,
+	    max_slides: 4,
+	    up: Vector{x: 0.0, y: -1.0},
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// CharacterController
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// A kinematic stadium (capsule) moved through static walls. Nothing pushes it,
+// it only goes where it's told, sliding along whatever it runs into. The
+// collider is private so it can only ever be the stadium it was made with.
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct CharacterController {
+    collider: Collider,
+    pub settings: CharacterSettings,
+    pub on_ground: bool,
+    pub ground_normal: Option<Vector>,
+}
+impl CharacterController {
+
+    pub fn new(stad: Stad, settings: CharacterSettings) -> CharacterController {
+	CharacterController {
+	    collider: Collider::stad(stad),
+	    settings,
+	    on_ground: false,
+	    ground_normal: None,
+	}
+    }
+
+    pub fn collider(&self) -> Collider {
+	self.collider
+    }
+
+    pub fn set_filter(&mut self, filter: CollisionFilter) {
+	self.collider.filter = filter;
+    }
+
+    pub fn stad(&self) -> Stad {
+	match self.collider.shape {
+	    Shape::Stad(s) => s,
+	    Shape::Circle(_) => unreachable!("only `new` sets the shape, and only to a stadium"),
+	}
+    }
+
+    pub fn is_walkable(&self, normal: Vector) -> bool {
+	normal.dot(self.settings.up) >= self.settings.max_slope.cos()
+    }
+
+    // Moves as far as it can towards `motion`, stepping up small ledges when
+    // on the ground, and returns how far it actually went.
+    pub fn move_by(&mut self, walls: &StaticGeometry, motion: Vector) -> Vector {
+	let start = self.stad().p1;
+	let up = self.settings.up;
+	let rise = motion.dot(up);
+	let across = motion.minus(up.scaled(rise));
+
+	let before = *self;
+	let slid = self.slide(walls, across);
+	if self.on_ground && slid.magnitude() < across.magnitude() - self.settings.skin {
+	    // blocked; see if it's something we can step up onto
+	    let mut stepped = before;
+	    stepped.slide(walls, up.scaled(self.settings.step_height));
+	    let went = stepped.slide(walls, across);
+	    stepped.slide(walls, up.scaled(-self.settings.step_height));
+	    stepped.detect_ground(walls);
+	    if stepped.on_ground && went.magnitude() > slid.magnitude() {
+		*self = stepped;
+	    }
+	}
+	self.slide(walls, up.scaled(rise));
+	self.detect_ground(walls);
+	start.towards(self.stad().p1)
+    }
+
+    // collide and slide: move until something's hit, take away the part of the
+    // motion going into it and carry on with the rest
+    fn slide(&mut self, walls: &StaticGeometry, motion: Vector) -> Vector {
+	let mut moved = Vector{x: 0.0, y: 0.0};
+	let mut remaining = motion;
+	for _ in 0..self.settings.max_slides {
+	    let len = remaining.magnitude();
+	    if len < 1e-9 {
+		break;
+	    }
+	    let hit = match walls.sweep(&self.collider, remaining) {
+		Some((_, hit)) => hit,
+		None => {
+		    self.translate(remaining);
+		    moved = moved.plus(remaining);
+		    break;
+		},
+	    };
+	    // stop a skin's width short
+	    let travel = (hit.t * len - self.settings.skin).max(0.0);
+	    let step = remaining.scaled(travel / len);
+	    self.translate(step);
+	    moved = moved.plus(step);
+
+	    let mut normal = hit.normal;
+	    if !self.is_walkable(normal) && normal.dot(self.settings.up) > 0.0 {
+		// too steep to walk, treat it as a wall rather than sliding up it
+		let flat = normal.minus(self.settings.up.scaled(normal.dot(self.settings.up)));
+		if flat.magnitude() > 1e-9 {
+		    normal = flat.normalized();
+		}
+	    }
+	    let rest = remaining.scaled(1.0 - travel / len);
+	    if self.is_walkable(normal) && rest.dot(self.settings.up) < 0.0 {
+		break; // landed, don't slide down the slope
+	    }
+	    remaining = rest.minus(normal.scaled(rest.dot(normal).min(0.0)));
+	}
+	moved
+    }
+
+    // Every wall the probe reaches is tried, not just the first: walking up to
+    // a ledge, its rounded corner can be nearer the feet than the floor is.
+    fn detect_ground(&mut self, walls: &StaticGeometry) {
+	let probe = self.settings.up.scaled(-(self.settings.ground_probe + self.settings.skin));
+	let mut candidates = vec![];
+	if self.collider.filter.allows(walls.filter) {
+	    let shape = self.collider.shape;
+	    walls.query_aabb(shape.aabb().union(shape.translated(probe).aabb()), &mut candidates);
+	    candidates.sort_unstable();
+	}
+	let ground = candidates.into_iter()
+	    .filter_map(|w| self.collider.shape.sweep(probe, Shape::Stad(walls.get(w))))
+	    .filter(|hit| self.is_walkable(hit.normal))
+	    .min_by(|a, b| a.t.total_cmp(&b.t))
+	    .map(|hit| hit.normal);
+	self.on_ground = ground.is_some();
+	self.ground_normal = ground;
+    }
+
+    fn translate(&mut self, v: Vector) {
+	self.collider.shape = self.collider.shape.translated(v);
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
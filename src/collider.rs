@@ -0,0 +1,146 @@
+use crate::geometry::*;
+use crate::tolerance::Tolerance;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Shape
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub enum Shape {
+    Circle(Circle),
+    Stad(Stad),
+}
+impl Shape {
+
+    // the narrow phase test; knows nothing about filtering
+    pub fn collides(&self, other: Shape) -> bool {
+	self.collides_with(other, Tolerance::default())
+    }
+
+    pub fn collides_with(&self, other: Shape, tolerance: Tolerance) -> bool {
+	match (*self, other) {
+	    (Shape::Circle(a), Shape::Circle(b)) => a.collides_circle_with(b, tolerance),
+	    (Shape::Circle(a), Shape::Stad(b))   => a.collides_stad_with(b, tolerance),
+	    (Shape::Stad(a),   Shape::Circle(b)) => b.collides_stad_with(a, tolerance),
+	    (Shape::Stad(a),   Shape::Stad(b))   => a.collides_stad_with(b, tolerance),
+	}
+    }
+
+    pub fn translated(&self, v: Vector) -> Shape {
+	match self {
+	    Shape::Circle(c) => Shape::Circle(c.translated(v)),
+	    Shape::Stad(s)   => Shape::Stad(s.translated(v)),
+	}
+    }
+}
+
+impl From<Circle> for Shape {
+    fn from(c: Circle) -> Shape {
+	Shape::Circle(c)
+    }
+}
+
+impl From<Stad> for Shape {
+    fn from(s: Stad) -> Shape {
+	Shape::Stad(s)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// CollisionFilter
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// `group` is the set of layers a collider lives on and `mask` is the set of
+// layers it is willing to touch. Two colliders only interact if each one's
+// group shows up in the other's mask.
+pub struct CollisionFilter {
+    pub group: u32,
+    pub mask: u32,
+}
+impl CollisionFilter {
+
+    pub const ALL: u32 = u32::MAX;
+
+    pub fn new(group: u32, mask: u32) -> CollisionFilter {
+	CollisionFilter {
+	    group,
+	    mask,
+	}
+    }
+
+    pub fn allows(&self, other: CollisionFilter) -> bool {
+	(self.group & other.mask) != 0 && (other.group & self.mask) != 0
+    }
+}
+
+impl Default for CollisionFilter {
+    // on the first layer and touches everything
+    fn default() -> CollisionFilter {
+	CollisionFilter::new(1, CollisionFilter::ALL)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Collider
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct Collider {
+    pub shape: Shape,
+    pub filter: CollisionFilter,
+    pub user_data: u64,
+    // sensors report overlaps (see sensor.rs) but never push anything, nor
+    // get pushed. A sensor's body in a Simulation still moves like any other
+    // (gravity pulls it, joints drag it), so put a trigger area that should
+    // stay put on a RigidBody::fixed.
+    pub sensor: bool,
+}
+impl Collider {
+
+    pub fn new(shape: Shape, filter: CollisionFilter, user_data: u64) -> Collider {
+	Collider {
+	    shape,
+	    filter,
+	    user_data,
+	    sensor: false,
+	}
+    }
+
+    pub fn sensor(shape: Shape, filter: CollisionFilter, user_data: u64) -> Collider {
+	Collider {
+	    sensor: true,
+	    ..Collider::new(shape, filter, user_data)
+	}
+    }
+
+    pub fn circle(c: Circle) -> Collider {
+	Collider::new(Shape::Circle(c), CollisionFilter::default(), 0)
+    }
+
+    pub fn stad(s: Stad) -> Collider {
+	Collider::new(Shape::Stad(s), CollisionFilter::default(), 0)
+    }
+
+    pub fn can_collide(&self, other: &Collider) -> bool {
+	self.filter.allows(other.filter)
+    }
+
+    // the filter is checked first so that rejected pairs never reach the
+    // narrow phase
+    pub fn collides(&self, other: &Collider) -> bool {
+	self.can_collide(other) && self.shape.collides(other.shape)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
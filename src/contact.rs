@@ -0,0 +1,125 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::tolerance::Tolerance;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Contact
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// `normal` is unit length and points from the first shape towards the second,
+// `depth` is how far they would have to move apart along it to only just
+// touch and `point` is halfway between the two surfaces.
+pub struct Contact {
+    pub normal: Vector,
+    pub depth: f64,
+    pub point: Point,
+}
+
+// Every shape here is some core (a point or a segment) inflated by a radius, so
+// once the nearest points of the two cores are known every pair of shapes
+// reduces to two circles. There's a contact whenever `collides` would say
+// so, which includes touching with a (clamped) depth of zero.
+fn contact_between_cores(pa: Point, ra: f64, pb: Point, rb: f64,
+			 tolerance: Tolerance) -> Option<Contact> {
+    let d = pa.towards(pb);
+    let dist = d.magnitude();
+    if !tolerance.classify(dist - ra - rb).is_touching() {
+	return None;
+    }
+    let normal = if dist > 0.0 {
+	d.normalized()
+    } else {
+	Vector{x: 1.0, y: 0.0} // cores touch, any direction is as good as another
+    };
+    let depth = (ra + rb - dist).max(0.0);
+    Some(Contact {
+	normal,
+	depth,
+	point: pa.plus(normal.scaled(ra - depth/2.0)),
+    })
+}
+
+impl Contact {
+
+    // the same contact seen from the other shape
+    pub fn flipped(&self) -> Contact {
+	Contact {
+	    normal: self.normal.scaled(-1.0),
+	    depth: self.depth,
+	    point: self.point,
+	}
+    }
+}
+
+// The `_with` versions take the tolerance that decides touching; the others
+// use the default one, as `collides` does.
+impl Circle {
+
+    pub fn contact_circle(&self, other: Circle) -> Option<Contact> {
+	self.contact_circle_with(other, Tolerance::default())
+    }
+
+    pub fn contact_stad(&self, other: Stad) -> Option<Contact> {
+	self.contact_stad_with(other, Tolerance::default())
+    }
+
+    pub fn contact_circle_with(&self, other: Circle, tolerance: Tolerance) -> Option<Contact> {
+	contact_between_cores(self.p, self.r, other.p, other.r, tolerance)
+    }
+
+    pub fn contact_stad_with(&self, other: Stad, tolerance: Tolerance) -> Option<Contact> {
+	let q = other.segment().closest_point(self.p);
+	contact_between_cores(self.p, self.r, q, other.r, tolerance)
+    }
+}
+
+impl Stad {
+
+    pub fn contact_circle(&self, other: Circle) -> Option<Contact> {
+	self.contact_circle_with(other, Tolerance::default())
+    }
+
+    pub fn contact_stad(&self, other: Stad) -> Option<Contact> {
+	self.contact_stad_with(other, Tolerance::default())
+    }
+
+    pub fn contact_circle_with(&self, other: Circle, tolerance: Tolerance) -> Option<Contact> {
+	other.contact_stad_with(*self, tolerance).map(|c| c.flipped())
+    }
+
+    pub fn contact_stad_with(&self, other: Stad, tolerance: Tolerance) -> Option<Contact> {
+	let (pa, pb) = self.segment().closest_points(other.segment());
+	contact_between_cores(pa, self.r, pb, other.r, tolerance)
+    }
+}
+
+impl Shape {
+
+    pub fn contact(&self, other: Shape) -> Option<Contact> {
+	self.contact_with(other, Tolerance::default())
+    }
+
+    pub fn contact_with(&self, other: Shape, tolerance: Tolerance) -> Option<Contact> {
+	match (*self, other) {
+	    (Shape::Circle(a), Shape::Circle(b)) => a.contact_circle_with(b, tolerance),
+	    (Shape::Circle(a), Shape::Stad(b))   => a.contact_stad_with(b, tolerance),
+	    (Shape::Stad(a),   Shape::Circle(b)) => a.contact_circle_with(b, tolerance),
+	    (Shape::Stad(a),   Shape::Stad(b))   => a.contact_stad_with(b, tolerance),
+	}
+    }
+}
+
+impl Collider {
+
+    pub fn contact(&self, other: &Collider) -> Option<Contact> {
+	if !self.can_collide(other) {
+	    return None;
+	}
+	self.shape.contact(other.shape)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,143 @@
+use crate::geometry::*;
+use crate::scene::*;
+
+// The drawing half of the circ_to_circ, circ_to_stad and stad_to_stad demos,
+// kept here so it can be rendered without a window (see tests/golden_images.rs).
+// Each demo works out for itself whether the shapes collide, since showing
+// how is the point of the demo, and passes that in.
+//
+// Only these three have construction lines to get wrong. The physics demos
+// (drop_box, ragdoll, soft_body and character) still draw straight to ggez
+// and have no scene here.
+
+fn shape_color(colliding: bool) -> Color {
+    if colliding {
+	Color::new(0.7, 0.45, 0.05, 0.8)
+    } else {
+	Color::new(0.05, 0.7, 0.25, 0.8)
+    }
+}
+
+const YELLOW: Color = Color { r: 0.9, g: 0.7, b: 0.15, a: 0.7 };
+const PURPLE: Color = Color { r: 0.5, g: 0.0, b: 0.8, a: 0.9 };
+const PURPLE2: Color = Color { r: 0.7, g: 0.1, b: 0.7, a: 0.9 };
+const GRAY: Color = Color { r: 0.5, g: 0.5, b: 0.5, a: 0.5 };
+
+// the black rings that show what can be dragged
+fn selector(scene: &mut Scene, p: Point) {
+    scene.ring(p, 15.0, 4.0, Color::BLACK);
+}
+
+// from `from`, r of the way towards `towards` (nothing if they're the same
+// point, when there's no way to go)
+fn radial_line(scene: &mut Scene, from: Point, towards: Point, r: f64, width: f64, color: Color) {
+    if let Ok(dir) = from.towards(towards).checked_normalized() {
+	scene.line(from, from.plus(dir.scaled(r)), width, color);
+    }
+}
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// circ_to_circ
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// The line between the centers and each circle's radius along it
+pub fn circ_to_circ(circs: [Circle; 2], colliding: bool) -> Scene {
+    let mut scene = Scene::new(Color::WHITE);
+    let [a, b] = circs;
+    scene.disc(a, shape_color(colliding));
+    scene.disc(b, shape_color(colliding));
+    scene.line(a.p, b.p, 8.0, YELLOW);
+    radial_line(&mut scene, a.p, b.p, a.r, 8.5, PURPLE);
+    radial_line(&mut scene, b.p, a.p, b.r, 8.5, PURPLE);
+    selector(&mut scene, a.p);
+    selector(&mut scene, b.p);
+    scene
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// circ_to_stad
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// The stadium's line extended past both ends, the circle's distance and
+// radii to each end, and its perpendicular distance and radii to the line.
+// A stadium with no length has no line, so it only gets the ends.
+pub fn circ_to_stad(circ: Circle, stad: Stad, colliding: bool) -> Scene {
+    let mut scene = Scene::new(Color::WHITE);
+    scene.disc(circ, shape_color(colliding));
+    scene.stad(stad, shape_color(colliding));
+
+    // a whole stad length (plus a diameter) past each end
+    let back = stad.p2.towards(stad.p1);
+    let along = back.checked_normalized().ok();
+    if let Some(dir) = along {
+	let reach = back.plus(dir.scaled(2.0*stad.r));
+	scene.line(stad.p1.plus(reach), stad.p2.minus(reach), 6.0, GRAY);
+    }
+
+    scene.line(circ.p, stad.p1, 8.0, YELLOW);
+    scene.line(circ.p, stad.p2, 8.0, YELLOW);
+
+    radial_line(&mut scene, circ.p, stad.p1, circ.r, 8.5, PURPLE);
+    radial_line(&mut scene, circ.p, stad.p2, circ.r, 8.5, PURPLE);
+    radial_line(&mut scene, stad.p1, circ.p, stad.r, 8.5, PURPLE);
+    radial_line(&mut scene, stad.p2, circ.p, stad.r, 8.5, PURPLE);
+
+    if along.is_some() {
+	let foot = stad.line().project(circ.p);
+	scene.line(foot, circ.p, 6.0, YELLOW);
+	radial_line(&mut scene, circ.p, foot, circ.r, 6.0, PURPLE2);
+	radial_line(&mut scene, foot, circ.p, stad.r, 6.0, PURPLE2);
+    }
+
+    selector(&mut scene, circ.p);
+    selector(&mut scene, stad.p1);
+    selector(&mut scene, stad.p2);
+    scene
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// stad_to_stad
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// The part of `line` inside a width by height window, if any. The line is
+// clipped to between the left and right edges and then to between the top
+// and bottom ones (Liang-Barsky), which is fine with lines through corners.
+pub fn visible_segment_from_line(line: Line, width: f64, height: f64) -> Option<Segment> {
+    let l = line.to_parametric();
+    let (mut t0, mut t1) = (f64::NEG_INFINITY, f64::INFINITY);
+    for &(origin, dir, max) in [(l.origin.x, l.dir.x, width), (l.origin.y, l.dir.y, height)].iter() {
+	if dir == 0.0 {
+	    if origin < 0.0 || origin > max {
+		return None;
+	    }
+	} else {
+	    let (a, b) = (-origin/dir, (max - origin)/dir);
+	    t0 = t0.max(a.min(b));
+	    t1 = t1.min(a.max(b));
+	}
+    }
+    if t0 < t1 {
+	Some(Segment { p1: l.at(t0), p2: l.at(t1) })
+    } else {
+	None
+    }
+}
+
+// both stadiums and the line through the first, across a width by height window
+pub fn stad_to_stad(a: Stad, b: Stad, colliding: bool, width: f64, height: f64) -> Scene {
+    let mut scene = Scene::new(Color::WHITE);
+    scene.stad(a, shape_color(colliding));
+    scene.stad(b, shape_color(colliding));
+    if let Some(s) = visible_segment_from_line(a.line(), width, height) {
+	scene.line(s.p1, s.p2, 2.0, YELLOW);
+    }
+    for p in [a.p1, a.p2, b.p1, b.p2].iter() {
+	selector(&mut scene, *p);
+    }
+    scene
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,209 @@
+use crate::geometry::*;
+use crate::body::*;
+use crate::broadphase;
+use crate::contact::Contact;
+use crate::solver::*;
+use crate::joint::*;
+use crate::island::*;
+use crate::sensor::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Simulation
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// a contact between bodies a and b with the normal pointing from a to b. `id`
+// is the manifold point's feature id, which tells apart the two points of a
+// side by side pair and matches them up from one step to the next.
+pub struct BodyContact {
+    pub a: usize,
+    pub b: usize,
+    pub id: u32,
+    pub contact: Contact,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Simulation {
+    pub bodies: Vec<RigidBody>,
+    pub gravity: Vector,
+    pub contacts: Vec<BodyContact>,
+    pub solver: ContactSolver,
+    pub joints: Vec<Joint>,
+    pub joint_solver: JointSolver,
+    pub sleep: SleepSettings,
+    // the islands as of the last step; sleeping islands are kept as they were
+    // when they fell asleep
+    pub islands: Vec<Vec<usize>>,
+    // Overlaps of sensor bodies with the others, by body index. Only the
+    // contacts skip sensors; gravity and joints move a sensor's body as usual.
+    pub sensors: SensorTracker<usize>,
+    // what the sensors saw during the last step
+    pub sensor_events: Vec<SensorEvent<usize>>,
+}
+impl Simulation {
+
+    pub fn new(gravity: Vector) -> Simulation {
+	Simulation {
+	    bodies: vec![],
+	    gravity,
+	    contacts: vec![],
+	    solver: ContactSolver::default(),
+	    joints: vec![],
+	    joint_solver: JointSolver::default(),
+	    sleep: SleepSettings::default(),
+	    islands: vec![],
+	    sensors: SensorTracker::new(),
+	    sensor_events: vec![],
+	}
+    }
+
+    pub fn add_body(&mut self, body: RigidBody) -> usize {
+	self.bodies.push(body);
+	self.bodies.len() - 1
+    }
+
+    pub fn add_joint(&mut self, joint: Joint) -> usize {
+	self.joints.push(joint);
+	self.joints.len() - 1
+    }
+
+    // Finds every touching pair of bodies (skipping pairs where neither body
+    // is awake, pairs their filters keep apart and sensors) using the
+    // broadphase and the contact manifolds. A pair can give one or two
+    // contacts.
+    pub fn find_contacts(&self) -> Vec<BodyContact> {
+	let colliders: Vec<_> = self.bodies.iter().map(|b| b.collider()).collect();
+	broadphase::candidate_pairs(&colliders).into_iter()
+	    .filter(|&(a, b)| self.bodies[a].is_awake() || self.bodies[b].is_awake())
+	    .filter(|&(a, b)| !colliders[a].sensor && !colliders[b].sensor)
+	    .filter_map(|(a, b)| {
+		colliders[a].shape.manifold(colliders[b].shape).map(|m| (a, b, m))
+	    })
+	    .flat_map(|(a, b, m)| {
+		m.contacts().map(|(id, contact)| BodyContact { a, b, id, contact }).collect::<Vec<_>>()
+	    })
+	    .collect()
+    }
+
+    // Semi-implicit Euler: velocities first, then the contact and joint
+    // solvers, then positions from the solved velocities. Sleeping bodies are
+    // left out until an awake body touches them.
+    pub fn step(&mut self, dt: f64) {
+	if !self.sleep.enabled {
+	    for body in self.bodies.iter_mut() {
+		body.wake();
+	    }
+	}
+	self.wake_islands();
+	self.contacts = self.find_contacts();
+	if self.wake_touched() {
+	    // the woken bodies' contacts with each other weren't looked for
+	    self.contacts = self.find_contacts();
+	}
+
+	for body in self.bodies.iter_mut().filter(|b| b.is_awake()) {
+	    body.velocity = body.velocity.plus(self.gravity.scaled(dt));
+	}
+
+	let bodies = &self.bodies;
+	let joints: Vec<Joint> = self.joints.iter()
+	    .filter(|j| bodies[j.a].is_awake() || bodies[j.b].is_awake())
+	    .copied().collect();
+	self.solver.begin(&mut self.bodies, &self.contacts, dt);
+	self.joint_solver.begin(&self.bodies, &joints, dt);
+	for _ in 0..self.solver.settings.iterations {
+	    self.joint_solver.iterate(&mut self.bodies);
+	    self.solver.iterate(&mut self.bodies);
+	}
+	self.solver.end();
+
+	for body in self.bodies.iter_mut().filter(|b| b.is_awake()) {
+	    body.position = body.position.plus(body.velocity.scaled(dt));
+	    body.angle += body.angular_velocity * dt;
+	}
+
+	self.update_sensors();
+	self.update_islands(&joints);
+	if self.sleep.enabled {
+	    self.fall_asleep(dt);
+	}
+    }
+
+    // (sensor, other) overlaps where at least one of them can move, diffed
+    // against the last step's
+    fn update_sensors(&mut self) {
+	let colliders: Vec<_> = self.bodies.iter().map(|b| b.collider()).collect();
+	let bodies = &self.bodies;
+	let overlaps = sensor_overlaps(&colliders).into_iter()
+	    .filter(|&(a, b)| !(bodies[a].is_fixed() && bodies[b].is_fixed()));
+	self.sensor_events = self.sensors.update(overlaps);
+    }
+
+    // Wakes every body in a sleeping island once any one of them has been
+    // woken (by `wake_touched` or from outside with `RigidBody::wake`).
+    // Returns whether any body woke.
+    fn wake_islands(&mut self) -> bool {
+	let mut woke = false;
+	for island in self.islands.iter() {
+	    let stirred = island.iter().any(|&i| !self.bodies[i].asleep);
+	    if stirred && island.iter().any(|&i| self.bodies[i].asleep) {
+		for &i in island.iter() {
+		    self.bodies[i].wake();
+		}
+		woke = true;
+	    }
+	}
+	woke
+    }
+
+    // wakes sleeping bodies that touch, or are jointed to, an awake body
+    fn wake_touched(&mut self) -> bool {
+	let links: Vec<_> = self.contacts.iter().map(|c| (c.a, c.b))
+	    .chain(self.joints.iter().map(|j| (j.a, j.b)))
+	    .collect();
+	let mut woke = false;
+	for (a, b) in links {
+	    for &(x, y) in [(a, b), (b, a)].iter() {
+		if self.bodies[x].is_awake() && self.bodies[y].asleep {
+		    self.bodies[y].wake();
+		    woke = true;
+		}
+	    }
+	}
+	self.wake_islands() || woke
+    }
+
+    fn update_islands(&mut self, joints: &[Joint]) {
+	let bodies = &self.bodies;
+	let mut islands: Vec<_> = self.islands.drain(..)
+	    .filter(|island| island.iter().all(|&i| bodies[i].asleep))
+	    .collect();
+	islands.extend(islands_of_awake(bodies, &self.contacts, joints));
+	self.islands = islands;
+    }
+
+    // An island falls asleep, all at once, when all of its bodies have been
+    // resting long enough. One body still moving keeps the whole island up.
+    fn fall_asleep(&mut self, dt: f64) {
+	let settings = self.sleep;
+	for body in self.bodies.iter_mut().filter(|b| b.is_awake()) {
+	    let resting = body.velocity.magnitude() < settings.linear_threshold
+		&& body.angular_velocity.abs() < settings.angular_threshold;
+	    body.sleep_time = if resting { body.sleep_time + dt } else { 0.0 };
+	}
+	for island in self.islands.iter() {
+	    if island.iter().all(|&i| self.bodies[i].sleep_time >= settings.time_to_sleep) {
+		for &i in island.iter() {
+		    let body = &mut self.bodies[i];
+		    body.asleep = true;
+		    body.velocity = Vector{x: 0.0, y: 0.0};
+		    body.angular_velocity = 0.0;
+		}
+	    }
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
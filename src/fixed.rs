@@ -0,0 +1,486 @@
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use crate::geometry::*;
+use crate::collider::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Fixed
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Everything in this file is integer arithmetic, so the same inputs give the
+// same bits on every machine and compiler, which lockstep networking needs
+// and f64 (fused multiply-adds, x87, libm's powf) doesn't promise.
+//
+// A Fixed is a 48.16 fixed point number: steps of 1/65536 and a range of
+// about +-1.4e14. Arithmetic saturates at the ends of the range instead of
+// panicking or wrapping around.
+//
+// The segment math multiplies squared lengths together (in 128 bits), which
+// is exact as long as coordinates stay within MAX_COORD of the origin. Past
+// that the answers are wrong, though still the same on every machine.
+pub const FRAC_BITS: u32 = 16;
+pub const MAX_COORD: i32 = 100_000;
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(PartialOrd)]
+#[derive(Ord)]
+#[derive(Hash)]
+#[derive(Default)]
+pub struct Fixed(i64);
+impl Fixed {
+
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(1 << FRAC_BITS);
+
+    pub fn from_raw(raw: i64) -> Fixed {
+	Fixed(raw)
+    }
+
+    pub fn raw(&self) -> i64 {
+	self.0
+    }
+
+    pub fn from_int(n: i32) -> Fixed {
+	Fixed((n as i64) << FRAC_BITS)
+    }
+
+    // Rounds to the nearest step. Converting is exact and deterministic in
+    // itself, but the f64 has to have been arrived at the same way everywhere.
+    pub fn from_f64(v: f64) -> Fixed {
+	Fixed((v * (1u64 << FRAC_BITS) as f64).round() as i64)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+	self.0 as f64 / (1u64 << FRAC_BITS) as f64
+    }
+
+    // rounds down; the square root of a negative number is taken to be zero
+    pub fn sqrt(&self) -> Fixed {
+	if self.0 <= 0 {
+	    return Fixed::ZERO;
+	}
+	Fixed((((self.0 as u128) << FRAC_BITS).isqrt()) as i64)
+    }
+
+    pub fn abs(&self) -> Fixed {
+	Fixed(self.0.saturating_abs())
+    }
+
+    pub fn clamp(&self, lo: Fixed, hi: Fixed) -> Fixed {
+	Fixed(self.0.clamp(lo.0, hi.0))
+    }
+}
+
+// clamps a wide intermediate result back into range
+fn saturate(raw: i128) -> Fixed {
+    Fixed(raw.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+    fn add(self, other: Fixed) -> Fixed {
+	Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, other: Fixed) -> Fixed {
+	Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed {
+	Fixed(self.0.saturating_neg())
+    }
+}
+
+// rounds towards negative infinity
+impl Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, other: Fixed) -> Fixed {
+	saturate((self.0 as i128 * other.0 as i128) >> FRAC_BITS)
+    }
+}
+
+// Rounds towards zero. Dividing by zero saturates to the largest value with
+// the sign of the numerator (zero over zero is zero) rather than panicking.
+impl Div for Fixed {
+    type Output = Fixed;
+    fn div(self, other: Fixed) -> Fixed {
+	if other.0 == 0 {
+	    return Fixed(match self.0 {
+		0 => 0,
+		n if n > 0 => i64::MAX,
+		_ => i64::MIN,
+	    });
+	}
+	saturate(((self.0 as i128) << FRAC_BITS) / other.0 as i128)
+    }
+}
+
+// p*q - r*s, kept at double the fraction bits so nothing is rounded away
+// and nothing overflows for coordinates within MAX_COORD
+fn wide_det(p: Fixed, q: Fixed, r: Fixed, s: Fixed) -> i128 {
+    p.0 as i128 * q.0 as i128 - r.0 as i128 * s.0 as i128
+}
+
+// num / den for two wide_det results, rounding towards zero
+fn ratio(num: i128, den: i128) -> Fixed {
+    saturate((num << FRAC_BITS) / den)
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// FixedVector
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub struct FixedVector {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+impl FixedVector {
+
+    pub fn from_vector(v: Vector) -> FixedVector {
+	FixedVector { x: Fixed::from_f64(v.x), y: Fixed::from_f64(v.y) }
+    }
+
+    pub fn to_vector(&self) -> Vector {
+	Vector { x: self.x.to_f64(), y: self.y.to_f64() }
+    }
+
+    pub fn magnitude(&self) -> Fixed {
+	self.dot(*self).sqrt()
+    }
+
+    pub fn scaled(&self, s: Fixed) -> FixedVector {
+	FixedVector { x: self.x * s, y: self.y * s }
+    }
+
+    // None for the zero vector, which has no direction
+    pub fn normalized(&self) -> Option<FixedVector> {
+	let m = self.magnitude();
+	if m == Fixed::ZERO {
+	    return None;
+	}
+	Some(FixedVector { x: self.x / m, y: self.y / m })
+    }
+
+    pub fn dot(&self, other: FixedVector) -> Fixed {
+	self.x*other.x + self.y*other.y
+    }
+
+    // z component of the 3d cross product
+    pub fn cross(&self, other: FixedVector) -> Fixed {
+	self.x*other.y - self.y*other.x
+    }
+
+    pub fn plus(&self, other: FixedVector) -> FixedVector {
+	FixedVector { x: self.x + other.x, y: self.y + other.y }
+    }
+
+    pub fn minus(&self, other: FixedVector) -> FixedVector {
+	FixedVector { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// FixedPoint
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub struct FixedPoint {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+impl FixedPoint {
+
+    pub fn from_point(p: Point) -> FixedPoint {
+	FixedPoint { x: Fixed::from_f64(p.x), y: Fixed::from_f64(p.y) }
+    }
+
+    pub fn to_point(&self) -> Point {
+	Point { x: self.x.to_f64(), y: self.y.to_f64() }
+    }
+
+    pub fn distance_squared(&self, other: FixedPoint) -> Fixed {
+	let d = self.towards(other);
+	d.dot(d)
+    }
+
+    pub fn distance(&self, other: FixedPoint) -> Fixed {
+	self.distance_squared(other).sqrt()
+    }
+
+    pub fn plus(&self, v: FixedVector) -> FixedPoint {
+	FixedPoint { x: self.x + v.x, y: self.y + v.y }
+    }
+
+    pub fn towards(&self, other: FixedPoint) -> FixedVector {
+	FixedVector { x: other.x - self.x, y: other.y - self.y }
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// FixedSegment
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub struct FixedSegment {
+    pub p1: FixedPoint,
+    pub p2: FixedPoint,
+}
+impl FixedSegment {
+
+    // same as Segment::closest_point
+    pub fn closest_point(&self, p: FixedPoint) -> FixedPoint {
+	let d = self.p1.towards(self.p2);
+	let len2 = d.dot(d);
+	if len2 == Fixed::ZERO {
+	    return self.p1;
+	}
+	let t = (self.p1.towards(p).dot(d) / len2).clamp(Fixed::ZERO, Fixed::ONE);
+	self.p1.plus(d.scaled(t))
+    }
+
+    // same as Segment::closest_points
+    pub fn closest_points(&self, other: FixedSegment) -> (FixedPoint, FixedPoint) {
+	let (zero, one) = (Fixed::ZERO, Fixed::ONE);
+	let d1 = self.p1.towards(self.p2);
+	let d2 = other.p1.towards(other.p2);
+	let r = other.p1.towards(self.p1);
+	let a = d1.dot(d1);
+	let e = d2.dot(d2);
+	let f = d2.dot(r);
+	if a == zero && e == zero {
+	    return (self.p1, other.p1);
+	}
+	if a == zero {
+	    return (self.p1, other.closest_point(self.p1));
+	}
+	if e == zero {
+	    return (self.closest_point(other.p1), other.p1);
+	}
+	let c = d1.dot(r);
+	let b = d1.dot(d2);
+	let denom = wide_det(a, e, b, b);
+	let mut s = if denom > 0 {
+	    ratio(wide_det(b, f, c, e), denom).clamp(zero, one)
+	} else {
+	    zero // parallel, any s will do
+	};
+	let mut t = (b*s + f) / e;
+	if t < zero {
+	    t = zero;
+	    s = (-c / a).clamp(zero, one);
+	} else if t > one {
+	    t = one;
+	    s = ((b - c) / a).clamp(zero, one);
+	}
+	(self.p1.plus(d1.scaled(s)), other.p1.plus(d2.scaled(t)))
+    }
+
+    pub fn distance_squared_to_point(&self, p: FixedPoint) -> Fixed {
+	self.closest_point(p).distance_squared(p)
+    }
+
+    pub fn distance_squared_to_segment(&self, other: FixedSegment) -> Fixed {
+	let (pa, pb) = self.closest_points(other);
+	pa.distance_squared(pb)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// FixedCircle, FixedStad, FixedShape
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub struct FixedCircle {
+    pub p: FixedPoint,
+    pub r: Fixed,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub struct FixedStad {
+    pub p1: FixedPoint,
+    pub p2: FixedPoint,
+    pub r: Fixed,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+pub enum FixedShape {
+    Circle(FixedCircle),
+    Stad(FixedStad),
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+// same conventions as Contact
+pub struct FixedContact {
+    pub normal: FixedVector,
+    pub depth: Fixed,
+    pub point: FixedPoint,
+}
+
+// The yes/no tests compare squared distances, so they need no square root.
+// Touching (distance exactly r1 + r2) is a collision, as with f64; there's
+// no epsilon since the default one is under one step of a Fixed.
+fn cores_collide(dist2: Fixed, ra: Fixed, rb: Fixed) -> bool {
+    let reach = ra + rb;
+    dist2 <= reach*reach
+}
+
+// same as contact_between_cores in contact.rs
+fn contact_between_cores(pa: FixedPoint, ra: Fixed, pb: FixedPoint, rb: Fixed)
+			 -> Option<FixedContact> {
+    let d = pa.towards(pb);
+    if !cores_collide(d.dot(d), ra, rb) {
+	return None;
+    }
+    let dist = d.magnitude();
+    let normal = d.normalized().unwrap_or(FixedVector { x: Fixed::ONE, y: Fixed::ZERO });
+    let depth = ra + rb - dist;
+    let half = Fixed::from_raw(depth.raw() / 2);
+    Some(FixedContact {
+	normal,
+	depth,
+	point: pa.plus(normal.scaled(ra - half)),
+    })
+}
+
+impl FixedCircle {
+
+    pub fn from_circle(c: Circle) -> FixedCircle {
+	FixedCircle { p: FixedPoint::from_point(c.p), r: Fixed::from_f64(c.r) }
+    }
+
+    pub fn collides_circle(&self, other: FixedCircle) -> bool {
+	cores_collide(self.p.distance_squared(other.p), self.r, other.r)
+    }
+
+    pub fn collides_stad(&self, other: FixedStad) -> bool {
+	cores_collide(other.segment().distance_squared_to_point(self.p), self.r, other.r)
+    }
+
+    pub fn contact_circle(&self, other: FixedCircle) -> Option<FixedContact> {
+	contact_between_cores(self.p, self.r, other.p, other.r)
+    }
+
+    pub fn contact_stad(&self, other: FixedStad) -> Option<FixedContact> {
+	let q = other.segment().closest_point(self.p);
+	contact_between_cores(self.p, self.r, q, other.r)
+    }
+}
+
+impl FixedStad {
+
+    pub fn from_stad(s: Stad) -> FixedStad {
+	FixedStad {
+	    p1: FixedPoint::from_point(s.p1),
+	    p2: FixedPoint::from_point(s.p2),
+	    r: Fixed::from_f64(s.r),
+	}
+    }
+
+    pub fn segment(&self) -> FixedSegment {
+	FixedSegment { p1: self.p1, p2: self.p2 }
+    }
+
+    pub fn collides_stad(&self, other: FixedStad) -> bool {
+	let dist2 = self.segment().distance_squared_to_segment(other.segment());
+	cores_collide(dist2, self.r, other.r)
+    }
+
+    pub fn contact_stad(&self, other: FixedStad) -> Option<FixedContact> {
+	let (pa, pb) = self.segment().closest_points(other.segment());
+	contact_between_cores(pa, self.r, pb, other.r)
+    }
+}
+
+impl FixedContact {
+
+    pub fn flipped(&self) -> FixedContact {
+	FixedContact {
+	    normal: FixedVector { x: -self.normal.x, y: -self.normal.y },
+	    depth: self.depth,
+	    point: self.point,
+	}
+    }
+}
+
+impl FixedShape {
+
+    pub fn from_shape(shape: Shape) -> FixedShape {
+	match shape {
+	    Shape::Circle(c) => FixedShape::Circle(FixedCircle::from_circle(c)),
+	    Shape::Stad(s)   => FixedShape::Stad(FixedStad::from_stad(s)),
+	}
+    }
+
+    pub fn collides(&self, other: FixedShape) -> bool {
+	match (*self, other) {
+	    (FixedShape::Circle(a), FixedShape::Circle(b)) => a.collides_circle(b),
+	    (FixedShape::Circle(a), FixedShape::Stad(b))   => a.collides_stad(b),
+	    (FixedShape::Stad(a),   FixedShape::Circle(b)) => b.collides_stad(a),
+	    (FixedShape::Stad(a),   FixedShape::Stad(b))   => a.collides_stad(b),
+	}
+    }
+
+    pub fn contact(&self, other: FixedShape) -> Option<FixedContact> {
+	match (*self, other) {
+	    (FixedShape::Circle(a), FixedShape::Circle(b)) => a.contact_circle(b),
+	    (FixedShape::Circle(a), FixedShape::Stad(b))   => a.contact_stad(b),
+	    (FixedShape::Stad(a),   FixedShape::Circle(b)) => b.contact_stad(a).map(|c| c.flipped()),
+	    (FixedShape::Stad(a),   FixedShape::Stad(b))   => a.contact_stad(b),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,755 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+#[cfg(feature = "ggez")]
+use ggez::mint::Point2;
+
+use crate::predicates::*;
+use crate::tolerance::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// GeometryError
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// what the checked constructors and operations below can go wrong with
+pub enum GeometryError {
+    NegativeRadius(f64),
+    // a NaN or infinite coordinate (or radius)
+    NonFinite,
+    // a direction was needed from a zero length vector or two equal points
+    DegenerateDirection,
+    // the lines never meet (or are the same line)
+    ParallelLines,
+    // fewer than three points, or all on one line
+    DegeneratePolygon,
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	match self {
+	    GeometryError::NegativeRadius(r) => write!(f, "negative radius {}", r),
+	    GeometryError::NonFinite => write!(f, "non-finite coordinate"),
+	    GeometryError::DegenerateDirection => write!(f, "zero length direction"),
+	    GeometryError::ParallelLines => write!(f, "parallel lines have no intersection"),
+	    GeometryError::DegeneratePolygon => write!(f, "polygon has no area"),
+	}
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+fn check_finite(values: &[f64]) -> Result<(), GeometryError> {
+    if values.iter().all(|v| v.is_finite()) {
+	Ok(())
+    } else {
+	Err(GeometryError::NonFinite)
+    }
+}
+
+fn check_radius(r: f64) -> Result<(), GeometryError> {
+    check_finite(&[r])?;
+    if r < 0.0 {
+	return Err(GeometryError::NegativeRadius(r));
+    }
+    Ok(())
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Vector
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+}
+impl Vector {
+
+    // hypot doesn't overflow or underflow partway for huge or tiny vectors
+    pub fn magnitude(&self) -> f64 {
+	self.x.hypot(self.y)
+    }
+
+    pub fn scaled(&self, s: f64) -> Vector {
+	Vector {
+	    x: self.x * s,
+	    y: self.y * s,
+	}
+    }
+
+    // (dividing rather than scaling by 1/magnitude, which overflows for
+    // tiny vectors)
+    pub fn normalized(&self) -> Vector {
+	let m = self.magnitude();
+	Vector {
+	    x: self.x/m,
+	    y: self.y/m,
+	}
+    }
+
+    // normalized, or an error instead of NaN for a zero (or non-finite) vector
+    pub fn checked_normalized(&self) -> Result<Vector, GeometryError> {
+	check_finite(&[self.x, self.y])?;
+	let m = self.magnitude();
+	if m == 0.0 {
+	    return Err(GeometryError::DegenerateDirection);
+	}
+	Ok(self.normalized())
+    }
+
+    pub fn slope(&self) -> f64 {
+	self.y/self.x
+    }
+
+    pub fn dot(&self, other: Vector) -> f64 {
+	self.x*other.x + self.y*other.y
+    }
+
+    // z component of the 3d cross product
+    pub fn cross(&self, other: Vector) -> f64 {
+	self.x*other.y - self.y*other.x
+    }
+
+    pub fn plus(&self, other: Vector) -> Vector {
+	Vector {
+	    x: self.x + other.x,
+	    y: self.y + other.y,
+	}
+    }
+
+    pub fn minus(&self, other: Vector) -> Vector {
+	Vector {
+	    x: self.x - other.x,
+	    y: self.y - other.y,
+	}
+    }
+
+    // counter-clockwise by `angle` radians (clockwise on screen, where y points down)
+    pub fn rotated(&self, angle: f64) -> Vector {
+	let (sin, cos) = angle.sin_cos();
+	Vector {
+	    x: self.x*cos - self.y*sin,
+	    y: self.x*sin + self.y*cos,
+	}
+    }
+
+    // rotated a quarter turn, (x, y) -> (-y, x)
+    pub fn perpendicular(&self) -> Vector {
+	Vector {
+	    x: -self.y,
+	    y: self.x,
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Point
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+impl Point {
+
+    #[cfg(feature = "ggez")]
+    pub fn mint(&self) -> Point2::<f32> {
+	Point2::<f32> {
+	    x: self.x as f32,
+	    y: self.y as f32,
+	}
+    }
+    
+    pub fn inverted(&self) -> Point {
+	Point {
+	    x: self.y,
+	    y: self.x,
+	}
+    }
+
+    pub fn distance(&self, other: Point) -> f64 {
+	(self.x - other.x).hypot(self.y - other.y)
+    }
+
+    pub fn plus(&self, other: Vector) -> Point {
+	Point {
+	    x: self.x + other.x,
+	    y: self.y + other.y,	    
+	}
+    }
+
+    pub fn minus(&self, other: Vector) -> Point {
+	Point {
+	    x: self.x - other.x,
+	    y: self.y - other.y,	    
+	}
+    }
+
+    pub fn towards(&self, other: Point) -> Vector {
+	Vector {
+	    x: other.x - self.x,
+	    y: other.y - self.y,
+	}
+    }
+
+    // t of the way from self to other
+    pub fn lerp(&self, other: Point, t: f64) -> Point {
+	self.plus(self.towards(other).scaled(t))
+    }
+
+    // Whether self is to the left of the line going the way it was built
+    // (the larger y side, for a line built left to right). Decided exactly
+    // from the line's coefficients, so it works for vertical lines and a
+    // point on the line is never above it.
+    pub fn above(&self, line: Line) -> bool {
+	line_side(line, *self) < 0.0
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Line
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// `ax + by + c = 0`, kept normalized: (a, b) is the unit normal, so
+// ax + by + c is the signed distance of (x, y) from the line. The constructors
+// below all keep it that way; a line written out by hand can be brought back
+// with normalized(). The normal points to the right of the direction the line
+// was built with (with y up), so points left of it have negative distances.
+pub struct Line {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+}
+impl Line {
+
+    // the same line scaled so that (a, b) is unit length
+    pub fn normalized(&self) -> Line {
+	let m = self.normal().magnitude();
+	Line {
+	    a: self.a/m,
+	    b: self.b/m,
+	    c: self.c/m,
+	}
+    }
+
+    // the line through p running along d (any length but zero)
+    pub fn from_point_direction(p: Point, d: Vector) -> Line {
+	let n = d.normalized();
+	Line {
+	    a: n.y,
+	    b: -n.x,
+	    c: -n.y*p.x + n.x*p.y,
+	}
+    }
+
+    // the line through p facing n (any length but zero)
+    pub fn from_normal(p: Point, n: Vector) -> Line {
+	let n = n.normalized();
+	Line {
+	    a: n.x,
+	    b: n.y,
+	    c: -(n.x*p.x + n.y*p.y),
+	}
+    }
+
+    // the line that passes through p1 and p2
+    pub fn from_point_point(p1: Point, p2: Point) -> Line {
+	Line::from_point_direction(p1, p1.towards(p2))
+    }
+
+    // from_point_point, or an error if the points are equal or not finite
+    pub fn checked_from_point_point(p1: Point, p2: Point) -> Result<Line, GeometryError> {
+	check_finite(&[p1.x, p1.y, p2.x, p2.y])?;
+	let niff = p1.towards(p2).checked_normalized()?;
+	Ok(Line::from_point_direction(p1, niff))
+    }
+
+    pub fn normal(&self) -> Vector {
+	Vector { x: self.a, y: self.b }
+    }
+
+    // unit length, with the normal on its right
+    pub fn direction(&self) -> Vector {
+	Vector { x: -self.b, y: self.a }
+    }
+
+    // The point where self and other intersect (via Cramer's rule)
+    pub fn intersection(&self, other: Line) -> Point {
+	let x = ( self.b*other.c - other.b*self.c) / (self.a*other.b - other.a*self.b);
+	let y = (-self.a*other.c + other.a*self.c) / (self.a*other.b - other.a*self.b);
+	Point{x, y}
+    }
+
+    // intersection, or an error where that would divide by zero (or
+    // otherwise come out infinite or NaN)
+    pub fn checked_intersection(&self, other: Line) -> Result<Point, GeometryError> {
+	check_finite(&[self.a, self.b, self.c, other.a, other.b, other.c])?;
+	if self.a*other.b - other.a*self.b == 0.0 {
+	    return Err(GeometryError::ParallelLines);
+	}
+	let p = self.intersection(other);
+	check_finite(&[p.x, p.y]).map_err(|_| GeometryError::ParallelLines)?;
+	Ok(p)
+    }
+
+    // The line that is perpendicular to self and passes through p
+    pub fn perpendicular_through(&self, p: Point) -> Line {
+	Line::from_point_direction(p, self.normal())
+    }
+
+    // positive on the side the normal faces
+    pub fn signed_distance(&self, p: Point) -> f64 {
+	self.a*p.x + self.b*p.y + self.c
+    }
+
+    pub fn distance_to_point(&self, p: Point) -> f64 {
+	self.signed_distance(p).abs()
+    }
+
+    // the point on the line nearest p
+    pub fn project(&self, p: Point) -> Point {
+	p.minus(self.normal().scaled(self.signed_distance(p)))
+    }
+
+    // p mirrored to the other side of the line
+    pub fn reflect(&self, p: Point) -> Point {
+	p.minus(self.normal().scaled(2.0*self.signed_distance(p)))
+    }
+
+    // v mirrored across the line's direction (e.g. a velocity bouncing off it)
+    pub fn reflect_vector(&self, v: Vector) -> Vector {
+	v.minus(self.normal().scaled(2.0*v.dot(self.normal())))
+    }
+
+    // starting from the point nearest the origin, going along direction()
+    pub fn to_parametric(&self) -> ParametricLine {
+	ParametricLine {
+	    origin: self.project(Point{x: 0.0, y: 0.0}),
+	    dir: self.direction(),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// ParametricLine
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// `origin + t*dir` for any t (see query::Ray for t >= 0 only). dir doesn't
+// need to be unit length, t is measured in multiples of it.
+pub struct ParametricLine {
+    pub origin: Point,
+    pub dir: Vector,
+}
+impl ParametricLine {
+
+    pub fn from_point_point(p1: Point, p2: Point) -> ParametricLine {
+	ParametricLine { origin: p1, dir: p1.towards(p2) }
+    }
+
+    pub fn at(&self, t: f64) -> Point {
+	self.origin.plus(self.dir.scaled(t))
+    }
+
+    // the t of the point nearest p
+    pub fn closest_t(&self, p: Point) -> f64 {
+	self.origin.towards(p).dot(self.dir) / self.dir.dot(self.dir)
+    }
+
+    // the point on the line nearest p
+    pub fn project(&self, p: Point) -> Point {
+	self.at(self.closest_t(p))
+    }
+
+    // the same line in normal form (the normal on the right of dir)
+    pub fn to_line(&self) -> Line {
+	Line::from_point_direction(self.origin, self.dir)
+    }
+
+    // the t where self crosses other, if they aren't parallel
+    pub fn intersection_t(&self, other: Line) -> Option<f64> {
+	let along = other.normal().dot(self.dir);
+	if along == 0.0 {
+	    return None;
+	}
+	Some(-other.signed_distance(self.origin) / along)
+    }
+}
+
+impl From<Segment> for ParametricLine {
+    // t = 0 at p1 and t = 1 at p2
+    fn from(s: Segment) -> ParametricLine {
+	ParametricLine::from_point_point(s.p1, s.p2)
+    }
+}
+
+impl From<ParametricLine> for Line {
+    fn from(l: ParametricLine) -> Line {
+	l.to_line()
+    }
+}
+
+impl From<Line> for ParametricLine {
+    fn from(l: Line) -> ParametricLine {
+	l.to_parametric()
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Segment
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct Segment {
+    pub p1: Point,
+    pub p2: Point,
+}
+impl Segment {
+
+    fn length(&self) -> f64 {
+	self.p1.distance(self.p2)
+    }
+
+    fn line(&self) -> Line {
+	Line::from_point_point(self.p1, self.p2)
+    }
+    
+    // the line perpendicular to the segment that passes through p
+    pub fn perpendicular_through(&self, p: Point) -> Line {
+	Line::from_normal(p, self.p1.towards(self.p2))
+    }
+
+    // the line perpendicular to the segment that passes through p1
+    pub fn perpendicular_1(&self) -> Line {
+	self.perpendicular_through(self.p1)
+    }
+    
+    // the line perpendicular to the segment that passes through p2
+    pub fn perpendicular_2(&self) -> Line {
+	self.perpendicular_through(self.p2)
+    }
+
+    // returns true if p falls within the shadow of self where you can imagine
+    // the segment casting a shadow on both sides perpendicular to itself such
+    // that the width of the shadow is the length of the segment. The edges of
+    // the shadow count as in it; a segment with no length casts no shadow.
+    pub fn shadows_point(&self, p: Point) -> bool {
+	self.p1 != self.p2 && dot2d(self.p1, self.p2, p) >= 0.0 && dot2d(self.p2, self.p1, p) >= 0.0
+    }
+
+    pub fn distance_to_point(&self, p: Point) -> f64 {
+	if self.shadows_point(p) {
+	    self.line().distance_to_point(p)
+	} else {
+	    self.p1.distance(p).min(self.p2.distance(p))
+	}
+    }
+
+    // the point on the segment nearest p
+    pub fn closest_point(&self, p: Point) -> Point {
+	let d = self.p1.towards(self.p2);
+	let len2 = d.dot(d);
+	if len2 == 0.0 {
+	    return self.p1;
+	}
+	let t = (self.p1.towards(p).dot(d) / len2).clamp(0.0, 1.0);
+	self.p1.plus(d.scaled(t))
+    }
+
+    // the pair of points (one on self, one on other) that are nearest each
+    // other. Works by clamping the parameters of the infinite lines rather
+    // than with slopes so it has no trouble with vertical or parallel segments
+    pub fn closest_points(&self, other: Segment) -> (Point, Point) {
+	let d1 = self.p1.towards(self.p2);
+	let d2 = other.p1.towards(other.p2);
+	let r = other.p1.towards(self.p1);
+	let a = d1.dot(d1);
+	let e = d2.dot(d2);
+	let f = d2.dot(r);
+	if a == 0.0 && e == 0.0 {
+	    return (self.p1, other.p1);
+	}
+	if a == 0.0 {
+	    return (self.p1, other.closest_point(self.p1));
+	}
+	if e == 0.0 {
+	    return (self.closest_point(other.p1), other.p1);
+	}
+	let c = d1.dot(r);
+	let b = d1.dot(d2);
+	let denom = a*e - b*b;
+	let mut s = if denom > 0.0 {
+	    ((b*f - c*e) / denom).clamp(0.0, 1.0)
+	} else {
+	    0.0 // parallel, any s will do
+	};
+	let mut t = (b*s + f) / e;
+	if t < 0.0 {
+	    t = 0.0;
+	    s = (-c / a).clamp(0.0, 1.0);
+	} else if t > 1.0 {
+	    t = 1.0;
+	    s = ((b - c) / a).clamp(0.0, 1.0);
+	}
+	(self.p1.plus(d1.scaled(s)), other.p1.plus(d2.scaled(t)))
+    }
+
+    // returns whichever end (p1 or p2) is nearest p, with the restriction that
+    // p must be on self.line() and p does not fall on the segment
+    pub fn near_point_along(&self, p: Point) -> Point {
+	if dot2d(self.p1, self.p2, p) < 0.0 {
+	    self.p1
+	} else {
+	    self.p2
+	}
+    }
+
+    // whether the segments cross or touch anywhere, endpoints included
+    pub fn is_overlap(&self, other: Segment) -> bool {
+	let o1 = orient2d(self.p1, self.p2, other.p1);
+	let o2 = orient2d(self.p1, self.p2, other.p2);
+	let o3 = orient2d(other.p1, other.p2, self.p1);
+	let o4 = orient2d(other.p1, other.p2, self.p2);
+	if o1 == 0.0 && o2 == 0.0 && o3 == 0.0 && o4 == 0.0 {
+	    // all on one line: they overlap if either has an end inside the other
+	    // (both pairs are checked since a segment with no length is on every
+	    // line through its point)
+	    let within = |s: &Segment, p: Point| p == s.p1 || s.shadows_point(p);
+	    return within(self, other.p1) || within(self, other.p2)
+		|| within(&other, self.p1) || within(&other, self.p2);
+	}
+	// each segment's ends are on opposite sides of (or on) the other's line
+	// (compared rather than multiplied, which could underflow to zero)
+	let opposite = |x: f64, y: f64| (x <= 0.0 && y >= 0.0) || (x >= 0.0 && y <= 0.0);
+	opposite(o1, o2) && opposite(o3, o4)
+    }
+    
+    // check every distance of point to point and point to line and return the smallest one
+    pub fn distance_to_segment(&self, other: Segment) -> f64 {
+	if self.is_overlap(other) {
+	    return 0.0;
+	}
+	let d1 = self.distance_to_point(other.p1);
+	let d2 = self.distance_to_point(other.p2);
+	let d3 = other.distance_to_point(self.p1);
+	let d4 = other.distance_to_point(self.p2);
+	// (f64::min rather than partial_cmp so a NaN can't panic)
+	[d1, d2, d3, d4].iter().cloned().fold(f64::INFINITY, f64::min)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Circ
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct Circle {
+    pub p: Point,
+    pub r: f64,
+}
+impl Circle {
+
+    // a circle, if the center is finite and the radius finite and not negative
+    pub fn try_new(p: Point, r: f64) -> Result<Circle, GeometryError> {
+	check_finite(&[p.x, p.y])?;
+	check_radius(r)?;
+	Ok(Circle { p, r })
+    }
+
+    // touching (within the default tolerance) counts
+    pub fn collides_circle(&self, other: Circle) -> bool {
+	self.collides_circle_with(other, Tolerance::default())
+    }
+
+    pub fn collides_stad(&self, other: Stad) -> bool {
+	self.collides_stad_with(other, Tolerance::default())
+    }
+
+    pub fn collides_circle_with(&self, other: Circle, tolerance: Tolerance) -> bool {
+	tolerance.classify(self.gap_circle(other)).is_touching()
+    }
+
+    pub fn collides_stad_with(&self, other: Stad, tolerance: Tolerance) -> bool {
+	tolerance.classify(self.gap_stad(other)).is_touching()
+    }
+
+    pub fn translated(&self, v: Vector) -> Circle {
+	Circle {
+	    p: self.p.plus(v),
+	    r: self.r,
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Stad
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct Stad {
+    pub p1: Point,
+    pub p2: Point,
+    pub r: f64,
+}
+impl Stad {
+
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64, r: f64) -> Stad {
+	Stad {
+	    p1: Point{x:x1, y:y1},
+	    p2: Point{x:x2, y:y2},
+	    r,
+	}
+    }
+    
+    // Like new but checked. Equal points are fine (the stad is then a circle).
+    pub fn try_new(p1: Point, p2: Point, r: f64) -> Result<Stad, GeometryError> {
+	check_finite(&[p1.x, p1.y, p2.x, p2.y])?;
+	check_radius(r)?;
+	Ok(Stad { p1, p2, r })
+    }
+
+    pub fn segment(&self) -> Segment {
+	Segment {
+	    p1: self.p1,
+	    p2: self.p2,
+	}
+    }
+
+    pub fn line(&self) -> Line {
+	Line::from_point_point(self.p1, self.p2)
+    }
+
+    // touching (within the default tolerance) counts
+    pub fn collides_stad(&self, other: Stad) -> bool {
+	self.collides_stad_with(other, Tolerance::default())
+    }
+
+    pub fn collides_stad_with(&self, other: Stad, tolerance: Tolerance) -> bool {
+	tolerance.classify(self.gap_stad(other)).is_touching()
+    }
+
+    pub fn translated(&self, v: Vector) -> Stad {
+	Stad {
+	    p1: self.p1.plus(v),
+	    p2: self.p2.plus(v),
+	    r: self.r,
+	}
+    }
+
+    // endpoint by endpoint, t of the way from self to other
+    pub fn lerp(&self, other: Stad, t: f64) -> Stad {
+	Stad {
+	    p1: self.p1.lerp(other.p1, t),
+	    p2: self.p2.lerp(other.p2, t),
+	    r: self.r + (other.r - self.r)*t,
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Polygon
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(PartialEq)]
+// A convex polygon. The points can wind either way round.
+pub struct Polygon {
+    pub points: Vec<Point>,
+}
+impl Polygon {
+
+    pub fn new(points: Vec<Point>) -> Polygon {
+	Polygon {
+	    points,
+	}
+    }
+
+    // new, if every point is finite and there are enough distinct ones to
+    // have an area
+    pub fn try_new(points: Vec<Point>) -> Result<Polygon, GeometryError> {
+	for p in points.iter() {
+	    check_finite(&[p.x, p.y])?;
+	}
+	let polygon = Polygon::new(points);
+	if polygon.points.len() < 3 || polygon.signed_area() == 0.0 {
+	    return Err(GeometryError::DegeneratePolygon);
+	}
+	Ok(polygon)
+    }
+
+    // the edge from point i to point i+1 (wrapping around)
+    pub fn edge(&self, i: usize) -> Segment {
+	Segment {
+	    p1: self.points[i],
+	    p2: self.points[(i + 1) % self.points.len()],
+	}
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+	(0..self.points.len()).map(move |i| self.edge(i))
+    }
+
+    // positive if the points wind from +x towards +y
+    pub fn signed_area(&self) -> f64 {
+	let origin = Point{x: 0.0, y: 0.0};
+	self.edges()
+	    .map(|e| origin.towards(e.p1).cross(origin.towards(e.p2)))
+	    .sum::<f64>() / 2.0
+    }
+
+    // unit normal of edge i pointing away from the polygon
+    pub fn outward_normal(&self, i: usize) -> Vector {
+	let e = self.edge(i);
+	let n = e.p1.towards(e.p2).normalized();
+	if self.signed_area() > 0.0 {
+	    Vector{x: n.y, y: -n.x}
+	} else {
+	    Vector{x: -n.y, y: n.x}
+	}
+    }
+
+    pub fn contains_point(&self, p: Point) -> bool {
+	(0..self.points.len()).all(|i| self.outward_normal(i).dot(self.points[i].towards(p)) < 0.0)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,214 @@
+use std::ops::{Add, Sub, Mul, Div};
+
+use crate::geometry::*;
+use crate::collider::*;
+use crate::broadphase;
+use crate::predicates::*;
+use crate::tolerance::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Interval
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// A range certain to hold the exact value of whatever was computed. Each
+// operation rounds to nearest like any f64 operation would and then widens
+// the result by an ulp each way, which covers the rounding since + - * / and
+// sqrt are all correctly rounded.
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+impl Interval {
+
+    // exactly v
+    pub fn point(v: f64) -> Interval {
+	Interval { lo: v, hi: v }
+    }
+
+    fn widened(lo: f64, hi: f64) -> Interval {
+	Interval { lo: lo.next_down(), hi: hi.next_up() }
+    }
+
+    pub fn contains(&self, v: f64) -> bool {
+	self.lo <= v && v <= self.hi
+    }
+
+    pub fn width(&self) -> f64 {
+	self.hi - self.lo
+    }
+
+    pub fn square(&self) -> Interval {
+	if self.contains(0.0) {
+	    let m = self.lo.abs().max(self.hi.abs());
+	    Interval { lo: 0.0, hi: (m*m).next_up() }
+	} else {
+	    *self * *self
+	}
+    }
+
+    // negative parts are taken as zero
+    pub fn sqrt(&self) -> Interval {
+	let lo = self.lo.max(0.0).sqrt().next_down().max(0.0);
+	Interval { lo, hi: self.hi.max(0.0).sqrt().next_up() }
+    }
+
+    pub fn min(&self, other: Interval) -> Interval {
+	Interval { lo: self.lo.min(other.lo), hi: self.hi.min(other.hi) }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    fn add(self, other: Interval) -> Interval {
+	Interval::widened(self.lo + other.lo, self.hi + other.hi)
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    fn sub(self, other: Interval) -> Interval {
+	Interval::widened(self.lo - other.hi, self.hi - other.lo)
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+    fn mul(self, other: Interval) -> Interval {
+	let products = [self.lo*other.lo, self.lo*other.hi, self.hi*other.lo, self.hi*other.hi];
+	let lo = products.iter().cloned().fold(f64::INFINITY, f64::min);
+	let hi = products.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+	Interval::widened(lo, hi)
+    }
+}
+
+// dividing by an interval containing zero gives everything
+impl Div for Interval {
+    type Output = Interval;
+    fn div(self, other: Interval) -> Interval {
+	if other.contains(0.0) {
+	    return Interval { lo: f64::NEG_INFINITY, hi: f64::INFINITY };
+	}
+	let quotients = [self.lo/other.lo, self.lo/other.hi, self.hi/other.lo, self.hi/other.hi];
+	let lo = quotients.iter().cloned().fold(f64::INFINITY, f64::min);
+	let hi = quotients.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+	Interval::widened(lo, hi)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Certified collisions
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+// Yes and No are what exact arithmetic would say; Uncertain means the exact
+// gap is too close to the tolerance band's edge for f64 to be sure which
+// side it's on, so the plain `collides` answer could go either way.
+pub enum Certainty {
+    Yes,
+    No,
+    Uncertain,
+}
+
+// Which end of a segment (if either) is nearest p is decided exactly with
+// dot2d. In between, the distance is the cross product over the length, so
+// there's no clamped parameter to carry through the interval math.
+fn point_segment_distance_squared(p: Point, s: Segment) -> Interval {
+    let (ax, ay) = (Interval::point(s.p1.x), Interval::point(s.p1.y));
+    let (px, py) = (Interval::point(p.x), Interval::point(p.y));
+    let to_end = |e: Point| (px - Interval::point(e.x)).square() + (py - Interval::point(e.y)).square();
+    if dot2d(s.p1, s.p2, p) <= 0.0 {
+	return to_end(s.p1);
+    }
+    if dot2d(s.p2, s.p1, p) <= 0.0 {
+	return to_end(s.p2);
+    }
+    let (dx, dy) = (Interval::point(s.p2.x) - ax, Interval::point(s.p2.y) - ay);
+    let cross = dx*(py - ay) - dy*(px - ax);
+    cross.square() / (dx.square() + dy.square())
+}
+
+// the same test `collides` makes (gap within the default tolerance), on an
+// interval that holds the exact gap
+fn certify(gap: Interval) -> Certainty {
+    let epsilon = Tolerance::default().epsilon;
+    if gap.hi <= epsilon {
+	Certainty::Yes
+    } else if gap.lo > epsilon {
+	Certainty::No
+    } else {
+	Certainty::Uncertain
+    }
+}
+
+fn gap(distance_squared: Interval, ra: f64, rb: f64) -> Interval {
+    distance_squared.sqrt() - (Interval::point(ra) + Interval::point(rb))
+}
+
+impl Circle {
+
+    pub fn collides_circle_certified(&self, other: Circle) -> Certainty {
+	let d2 = point_segment_distance_squared(self.p, Segment { p1: other.p, p2: other.p });
+	certify(gap(d2, self.r, other.r))
+    }
+
+    pub fn collides_stad_certified(&self, other: Stad) -> Certainty {
+	let d2 = point_segment_distance_squared(self.p, other.segment());
+	certify(gap(d2, self.r, other.r))
+    }
+}
+
+impl Stad {
+
+    pub fn collides_circle_certified(&self, other: Circle) -> Certainty {
+	other.collides_stad_certified(*self)
+    }
+
+    // Crossing segments are found exactly (with orient2d); otherwise the
+    // nearest points involve an end of one of them, so the distance is the
+    // least of the four end to segment distances.
+    pub fn collides_stad_certified(&self, other: Stad) -> Certainty {
+	let (a, b) = (self.segment(), other.segment());
+	let d2 = if a.is_overlap(b) {
+	    Interval::point(0.0)
+	} else {
+	    point_segment_distance_squared(a.p1, b)
+		.min(point_segment_distance_squared(a.p2, b))
+		.min(point_segment_distance_squared(b.p1, a))
+		.min(point_segment_distance_squared(b.p2, a))
+	};
+	certify(gap(d2, self.r, other.r))
+    }
+}
+
+impl Shape {
+
+    pub fn collides_certified(&self, other: Shape) -> Certainty {
+	match (*self, other) {
+	    (Shape::Circle(a), Shape::Circle(b)) => a.collides_circle_certified(b),
+	    (Shape::Circle(a), Shape::Stad(b))   => a.collides_stad_certified(b),
+	    (Shape::Stad(a),   Shape::Circle(b)) => a.collides_circle_certified(b),
+	    (Shape::Stad(a),   Shape::Stad(b))   => a.collides_stad_certified(b),
+	}
+    }
+}
+
+// For level checks: every pair (as in broadphase::candidate_pairs) whose
+// collision answer can't be trusted, so it can be nudged apart or together.
+pub fn uncertain_pairs(colliders: &[Collider]) -> Vec<(usize, usize)> {
+    broadphase::candidate_pairs(colliders).into_iter()
+	.filter(|&(a, b)| {
+	    colliders[a].shape.collides_certified(colliders[b].shape) == Certainty::Uncertain
+	})
+	.collect()
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,90 @@
+use crate::body::*;
+use crate::dynamics::BodyContact;
+use crate::joint::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SleepSettings
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct SleepSettings {
+    pub enabled: bool,
+    // a body slower than both of these is resting
+    pub linear_threshold: f64,
+    pub angular_threshold: f64,
+    // an island falls asleep once every body in it has rested this long
+    pub time_to_sleep: f64,
+}
+
+impl Default for SleepSettings {
+    fn default() -> SleepSettings {
+	SleepSettings {
+	    enabled: true,
+	    linear_threshold: 8.0,
+	    angular_threshold: 0.1,
+	    time_to_sleep: 0.5,
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Islands
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// union-find over body indices
+struct Sets {
+    parent: Vec<usize>,
+}
+impl Sets {
+
+    fn new(n: usize) -> Sets {
+	Sets { parent: (0..n).collect() }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+	while self.parent[i] != i {
+	    self.parent[i] = self.parent[self.parent[i]];
+	    i = self.parent[i];
+	}
+	i
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+	let (a, b) = (self.find(a), self.find(b));
+	self.parent[a] = b;
+    }
+}
+
+// Groups the awake bodies that touch (or are jointed), directly or through
+// other awake bodies, into islands. Fixed bodies don't join islands, so two
+// piles on the same floor are separate islands, and they aren't in any island
+// themselves. Each island is sorted, and the islands are in order of their
+// lowest body.
+pub fn islands_of_awake(bodies: &[RigidBody], contacts: &[BodyContact], joints: &[Joint]) -> Vec<Vec<usize>> {
+    let mut sets = Sets::new(bodies.len());
+    let links = contacts.iter().map(|c| (c.a, c.b))
+	.chain(joints.iter().map(|j| (j.a, j.b)));
+    for (a, b) in links {
+	if bodies[a].is_awake() && bodies[b].is_awake() {
+	    sets.join(a, b);
+	}
+    }
+
+    // island index by root
+    let mut index = vec![usize::MAX; bodies.len()];
+    let mut islands: Vec<Vec<usize>> = vec![];
+    for i in (0..bodies.len()).filter(|&i| bodies[i].is_awake()) {
+	let root = sets.find(i);
+	if index[root] == usize::MAX {
+	    index[root] = islands.len();
+	    islands.push(vec![]);
+	}
+	islands[index[root]].push(i);
+    }
+    islands
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,215 @@
+use crate::geometry::*;
+use crate::body::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Joint
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+pub enum JointKind {
+    // the anchors stay together, the bodies turn freely about them
+    Revolute,
+    // the anchors stay `length` apart
+    Distance { length: f64 },
+    // the anchors stay together and the bodies can't turn relative to each other
+    Weld { reference_angle: f64 },
+    // b slides along `axis` (unit length, in a's frame) without turning
+    Prismatic { axis: Vector, reference_angle: f64 },
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// A constraint between bodies a and b. The anchors are in each body's own
+// frame (see `RigidBody::to_local`) so they move and turn with the body.
+pub struct Joint {
+    pub a: usize,
+    pub b: usize,
+    pub anchor_a: Point,
+    pub anchor_b: Point,
+    pub kind: JointKind,
+}
+impl Joint {
+
+    pub fn revolute(bodies: &[RigidBody], a: usize, b: usize, anchor: Point) -> Joint {
+	Joint {
+	    a,
+	    b,
+	    anchor_a: bodies[a].to_local(anchor),
+	    anchor_b: bodies[b].to_local(anchor),
+	    kind: JointKind::Revolute,
+	}
+    }
+
+    // keeps the anchors as far apart as they are now
+    pub fn distance(bodies: &[RigidBody], a: usize, b: usize,
+		    anchor_a: Point, anchor_b: Point) -> Joint {
+	Joint {
+	    a,
+	    b,
+	    anchor_a: bodies[a].to_local(anchor_a),
+	    anchor_b: bodies[b].to_local(anchor_b),
+	    kind: JointKind::Distance { length: anchor_a.distance(anchor_b) },
+	}
+    }
+
+    pub fn weld(bodies: &[RigidBody], a: usize, b: usize, anchor: Point) -> Joint {
+	Joint {
+	    kind: JointKind::Weld { reference_angle: bodies[b].angle - bodies[a].angle },
+	    ..Joint::revolute(bodies, a, b, anchor)
+	}
+    }
+
+    // `axis` is in world space, as things are now
+    pub fn prismatic(bodies: &[RigidBody], a: usize, b: usize,
+		     anchor: Point, axis: Vector) -> Joint {
+	Joint {
+	    kind: JointKind::Prismatic {
+		axis: axis.normalized().rotated(-bodies[a].angle),
+		reference_angle: bodies[b].angle - bodies[a].angle,
+	    },
+	    ..Joint::revolute(bodies, a, b, anchor)
+	}
+    }
+
+    pub fn world_anchors(&self, bodies: &[RigidBody]) -> (Point, Point) {
+	(bodies[self.a].to_world(self.anchor_a), bodies[self.b].to_world(self.anchor_b))
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// JointSolver
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// One scalar constraint. Linear rows keep the anchors' relative velocity along
+// `dir` at zero, angular rows keep the relative spin at zero. The bias feeds
+// back the position error the same way contacts do.
+struct Row {
+    a: usize,
+    b: usize,
+    pa: Point,
+    pb: Point,
+    dir: Option<Vector>,
+    mass: f64,
+    bias: f64,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct JointSolver {
+    pub baumgarte: f64,
+    rows: Vec<Row>,
+}
+
+impl Default for JointSolver {
+    fn default() -> JointSolver {
+	JointSolver {
+	    baumgarte: 0.2,
+	    rows: vec![],
+	}
+    }
+}
+
+impl JointSolver {
+
+    // breaks the joints down into rows for this step
+    pub fn begin(&mut self, bodies: &[RigidBody], joints: &[Joint], dt: f64) {
+	self.rows.clear();
+	for j in joints {
+	    let (pa, pb) = j.world_anchors(bodies);
+	    let gap = pa.towards(pb);
+	    let turned = bodies[j.b].angle - bodies[j.a].angle;
+	    let pin = [Vector{x: 1.0, y: 0.0}, Vector{x: 0.0, y: 1.0}];
+	    match j.kind {
+		JointKind::Revolute => {
+		    for dir in pin.iter() {
+			self.push_linear(bodies, j, pa, pb, *dir, gap.dot(*dir), dt);
+		    }
+		},
+		JointKind::Distance { length } => {
+		    let len = gap.magnitude();
+		    if len > 0.0 {
+			self.push_linear(bodies, j, pa, pb, gap.scaled(1.0/len), len - length, dt);
+		    }
+		},
+		JointKind::Weld { reference_angle } => {
+		    for dir in pin.iter() {
+			self.push_linear(bodies, j, pa, pb, *dir, gap.dot(*dir), dt);
+		    }
+		    self.push_angular(bodies, j, turned - reference_angle, dt);
+		},
+		JointKind::Prismatic { axis, reference_angle } => {
+		    let across = axis.rotated(bodies[j.a].angle).perpendicular();
+		    self.push_linear(bodies, j, pa, pb, across, gap.dot(across), dt);
+		    self.push_angular(bodies, j, turned - reference_angle, dt);
+		},
+	    }
+	}
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_linear(&mut self, bodies: &[RigidBody], j: &Joint, pa: Point, pb: Point,
+		   dir: Vector, error: f64, dt: f64) {
+	let (a, b) = (&bodies[j.a], &bodies[j.b]);
+	let ran = a.position.towards(pa).cross(dir);
+	let rbn = b.position.towards(pb).cross(dir);
+	let k = a.inv_mass + b.inv_mass + ran*ran*a.inv_inertia + rbn*rbn*b.inv_inertia;
+	if k == 0.0 {
+	    return;
+	}
+	self.rows.push(Row {
+	    a: j.a,
+	    b: j.b,
+	    pa,
+	    pb,
+	    dir: Some(dir),
+	    mass: 1.0/k,
+	    bias: self.baumgarte / dt * error,
+	});
+    }
+
+    fn push_angular(&mut self, bodies: &[RigidBody], j: &Joint, error: f64, dt: f64) {
+	let k = bodies[j.a].inv_inertia + bodies[j.b].inv_inertia;
+	if k == 0.0 {
+	    return;
+	}
+	self.rows.push(Row {
+	    a: j.a,
+	    b: j.b,
+	    pa: bodies[j.a].position,
+	    pb: bodies[j.b].position,
+	    dir: None,
+	    mass: 1.0/k,
+	    bias: self.baumgarte / dt * error,
+	});
+    }
+
+    // one pass over every row
+    pub fn iterate(&mut self, bodies: &mut [RigidBody]) {
+	for row in self.rows.iter() {
+	    match row.dir {
+		Some(dir) => {
+		    let v = bodies[row.b].velocity_at(row.pb).minus(bodies[row.a].velocity_at(row.pa));
+		    let lambda = -row.mass * (v.dot(dir) + row.bias);
+		    bodies[row.a].apply_impulse(dir.scaled(-lambda), row.pa);
+		    bodies[row.b].apply_impulse(dir.scaled(lambda), row.pb);
+		},
+		None => {
+		    let spin = bodies[row.b].angular_velocity - bodies[row.a].angular_velocity;
+		    let lambda = -row.mass * (spin + row.bias);
+		    bodies[row.a].angular_velocity -= lambda * bodies[row.a].inv_inertia;
+		    bodies[row.b].angular_velocity += lambda * bodies[row.b].inv_inertia;
+		},
+	    }
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,28 @@
+pub mod geometry;
+pub mod collider;
+pub mod broadphase;
+pub mod contact;
+pub mod batch;
+pub mod simd;
+pub mod query;
+pub mod world;
+pub mod static_geometry;
+pub mod body;
+pub mod dynamics;
+pub mod solver;
+pub mod manifold;
+pub mod sweep;
+pub mod character;
+pub mod joint;
+pub mod island;
+pub mod fixed;
+pub mod soft;
+pub mod sensor;
+pub mod stepper;
+pub mod predicates;
+pub mod tolerance;
+pub mod interval;
+pub mod area;
+pub mod scene;
+pub mod demo_scenes;
+pub mod raster;
@@ -0,0 +1,249 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::contact::*;
+use crate::tolerance::Tolerance;
+
+// Segments within this much (the sine of the angle between them) of parallel
+// get a two point manifold
+const PARALLEL_TOLERANCE: f64 = 0.05;
+
+// id of the point in a manifold that only has the one nearest point
+pub const SINGLE_POINT: u32 = u32::MAX;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Manifold
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// `id` names the features the point was built from (see the functions below)
+// so the same point can be recognised from one step to the next.
+pub struct ManifoldPoint {
+    pub point: Point,
+    pub depth: f64,
+    pub id: u32,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// One or two points sharing a normal, which points from the first shape
+// towards the second
+pub struct Manifold {
+    pub normal: Vector,
+    points: [ManifoldPoint; 2],
+    len: usize,
+}
+impl Manifold {
+
+    fn empty(normal: Vector) -> Manifold {
+	let none = ManifoldPoint { point: Point{x: 0.0, y: 0.0}, depth: 0.0, id: 0 };
+	Manifold {
+	    normal,
+	    points: [none, none],
+	    len: 0,
+	}
+    }
+
+    fn push(&mut self, p: ManifoldPoint) {
+	self.points[self.len] = p;
+	self.len += 1;
+    }
+
+    pub fn single(c: Contact) -> Manifold {
+	let mut m = Manifold::empty(c.normal);
+	m.push(ManifoldPoint { point: c.point, depth: c.depth, id: SINGLE_POINT });
+	m
+    }
+
+    pub fn points(&self) -> &[ManifoldPoint] {
+	&self.points[..self.len]
+    }
+
+    pub fn contacts(&self) -> impl Iterator<Item = (u32, Contact)> + '_ {
+	self.points().iter().map(move |p| (p.id, Contact {
+	    normal: self.normal,
+	    depth: p.depth,
+	    point: p.point,
+	}))
+    }
+
+    pub fn flipped(&self) -> Manifold {
+	Manifold {
+	    normal: self.normal.scaled(-1.0),
+	    ..*self
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Clipping
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+
+// `reference` and `incident` are near parallel cores. The incident segment is
+// clipped to the extent of the reference one (measured along the reference)
+// and each end of what's left becomes a point, as long as the two surfaces
+// actually overlap there. `normal` points from the reference towards the
+// incident. The ids are `id_base` plus 0/1 when the end came from the
+// reference's p1/p2 and 2/3 when it came from the incident's p1/p2.
+fn clip(reference: Segment, ref_r: f64, incident: Segment, inc_r: f64,
+	normal: Vector, id_base: u32) -> Manifold {
+    let mut m = Manifold::empty(normal);
+    let along = reference.p1.towards(reference.p2);
+    let len = along.magnitude();
+    let u = along.scaled(1.0/len);
+    let t1 = reference.p1.towards(incident.p1).dot(u);
+    let t2 = reference.p1.towards(incident.p2).dot(u);
+    if t1 == t2 {
+	return m;
+    }
+
+    let (inc_lo, inc_hi) = if t1 < t2 { ((t1, 2), (t2, 3)) } else { ((t2, 3), (t1, 2)) };
+    let lo = if inc_lo.0 > 0.0 { inc_lo } else { (0.0, 0) };
+    let hi = if inc_hi.0 < len { inc_hi } else { (len, 1) };
+    if hi.0 <= lo.0 {
+	return m;
+    }
+
+    for &(t, id) in [lo, hi].iter() {
+	let on_ref = reference.p1.plus(u.scaled(t));
+	let on_inc = incident.p1.plus(incident.p1.towards(incident.p2).scaled((t - t1)/(t2 - t1)));
+	let depth = ref_r + inc_r - on_ref.towards(on_inc).dot(normal);
+	if depth > 0.0 {
+	    // halfway between the two surfaces
+	    let surface_ref = on_ref.plus(normal.scaled(ref_r));
+	    let surface_inc = on_inc.minus(normal.scaled(inc_r));
+	    m.push(ManifoldPoint {
+		point: surface_ref.plus(surface_ref.towards(surface_inc).scaled(0.5)),
+		depth,
+		id: id_base + id,
+	    });
+	}
+    }
+    m
+}
+
+fn near_parallel(a: Vector, b: Vector) -> bool {
+    let (la, lb) = (a.magnitude(), b.magnitude());
+    la > 0.0 && lb > 0.0 && (a.cross(b) / (la*lb)).abs() < PARALLEL_TOLERANCE
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Manifolds
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+impl Stad {
+
+    // Two points when the stadiums lie side by side with near parallel cores,
+    // otherwise the single nearest point. Point ids are 0/1 for self's p1/p2,
+    // 2/3 for other's p1/p2 (whichever endpoint bounds that end of the
+    // overlap) or SINGLE_POINT.
+    pub fn manifold_stad(&self, other: Stad) -> Option<Manifold> {
+	let c = self.contact_stad(other)?;
+	let along = self.p1.towards(self.p2);
+	if near_parallel(along, other.p1.towards(other.p2)) {
+	    let mut n = along.normalized().perpendicular();
+	    if n.dot(c.normal) < 0.0 {
+		n = n.scaled(-1.0);
+	    }
+	    // end to end they're better off with the single point
+	    if n.dot(c.normal) > 1.0 - PARALLEL_TOLERANCE {
+		let m = clip(self.segment(), self.r, other.segment(), other.r, n, 0);
+		if m.len > 0 {
+		    return Some(m);
+		}
+	    }
+	}
+	Some(Manifold::single(c))
+    }
+
+    // The polygon is treated as having no radius. Two points when the core
+    // lies flat against one of the polygon's edges; their ids are 4*edge plus
+    // 0/1 when the end came from the edge's first/second point and 2/3 when
+    // it came from self's p1/p2.
+    //
+    // Shape has no polygon variant, so neither Shape::manifold nor the
+    // Simulation ever gets here; this is for callers resolving stadiums
+    // against polygon level geometry themselves.
+    pub fn manifold_polygon(&self, poly: &Polygon) -> Option<Manifold> {
+	let seg = self.segment();
+
+	// the edge the core is furthest outside of, as in the separating axis test
+	let (face, face_sep) = (0..poly.points.len())
+	    .map(|i| {
+		let n = poly.outward_normal(i);
+		let v = poly.points[i];
+		(i, v.towards(seg.p1).dot(n).min(v.towards(seg.p2).dot(n)))
+	    })
+	    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+	let tolerance = Tolerance::default();
+	if !tolerance.classify(face_sep - self.r).is_touching() {
+	    return None;
+	}
+
+	let (on_seg, on_poly) = poly.edges()
+	    .map(|e| seg.closest_points(e))
+	    .min_by(|a, b| a.0.distance(a.1).total_cmp(&b.0.distance(b.1)))?;
+	let dist = on_seg.distance(on_poly);
+	let core_inside = dist == 0.0 || poly.contains_point(seg.p1);
+	if !core_inside && !tolerance.classify(dist - self.r).is_touching() {
+	    return None;
+	}
+
+	let normal = if core_inside {
+	    poly.outward_normal(face).scaled(-1.0)
+	} else {
+	    on_seg.towards(on_poly).scaled(1.0/dist)
+	};
+
+	// the edge facing us; if the core lies along it use both ends
+	let (reference, facing) = (0..poly.points.len())
+	    .map(|i| (i, poly.outward_normal(i).dot(normal.scaled(-1.0))))
+	    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+	let edge = poly.edge(reference);
+	if facing > 1.0 - PARALLEL_TOLERANCE && near_parallel(edge.p1.towards(edge.p2), seg.p1.towards(seg.p2)) {
+	    let m = clip(edge, 0.0, seg, self.r, poly.outward_normal(reference), 4*reference as u32);
+	    if m.len > 0 {
+		return Some(m.flipped());
+	    }
+	}
+
+	let c = if core_inside {
+	    let n = poly.outward_normal(face);
+	    let v = poly.points[face];
+	    let deepest = if v.towards(seg.p1).dot(n) <= v.towards(seg.p2).dot(n) {
+		seg.p1
+	    } else {
+		seg.p2
+	    };
+	    Contact { normal, depth: (self.r - face_sep).max(0.0), point: deepest }
+	} else {
+	    let surface = on_seg.plus(normal.scaled(self.r));
+	    Contact {
+		normal,
+		depth: (self.r - dist).max(0.0),
+		point: surface.plus(surface.towards(on_poly).scaled(0.5)),
+	    }
+	};
+	Some(Manifold::single(c))
+    }
+}
+
+impl Shape {
+
+    pub fn manifold(&self, other: Shape) -> Option<Manifold> {
+	match (*self, other) {
+	    (Shape::Stad(a), Shape::Stad(b)) => a.manifold_stad(b),
+	    _ => self.contact(other).map(Manifold::single),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,136 @@
+use crate::geometry::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Exact arithmetic
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// After Shewchuk, "Adaptive Precision Floating-Point Arithmetic and Fast
+// Robust Geometric Predicates". A number is kept as an expansion: a sum of
+// f64s that don't overlap, smallest first, which holds sums and products of
+// f64s with no rounding at all. Only the sign is ever needed, and that's the
+// sign of the largest (last nonzero) component.
+
+// half an ulp of 1.0
+const EPSILON: f64 = f64::EPSILON / 2.0;
+// if the rounded determinant is bigger than this times the sum of the sizes
+// of its two products, its sign is right
+const ERRBOUND_A: f64 = (3.0 + 16.0*EPSILON) * EPSILON;
+
+// a + b == x + y exactly, with x the rounded sum
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bv = x - a;
+    let av = x - bv;
+    (x, (a - av) + (b - bv))
+}
+
+// a * b == x + y exactly, with x the rounded product (the fused multiply-add
+// rounds only once, so it gives back exactly what rounding took off)
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
+struct Expansion {
+    components: Vec<f64>,
+}
+impl Expansion {
+
+    // Shewchuk's Grow-Expansion, dropping zeros as it goes
+    fn add(&mut self, b: f64) {
+	let mut q = b;
+	let mut grown = Vec::with_capacity(self.components.len() + 1);
+	for &e in self.components.iter() {
+	    let (sum, err) = two_sum(q, e);
+	    if err != 0.0 {
+		grown.push(err);
+	    }
+	    q = sum;
+	}
+	if q != 0.0 {
+	    grown.push(q);
+	}
+	self.components = grown;
+    }
+
+    fn add_product(&mut self, a: f64, b: f64) {
+	let (x, y) = two_product(a, b);
+	self.add(y);
+	self.add(x);
+    }
+
+    // -1.0, 0.0 or 1.0
+    fn sign(&self) -> f64 {
+	match self.components.last() {
+	    Some(&top) => top.signum(),
+	    None => 0.0,
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Predicates
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Each of these returns a number whose sign is always right, even when the
+// points are (or are within rounding of) exactly on the boundary. Most calls
+// are decided by the plain f64 arithmetic; only the close calls fall back to
+// exact arithmetic. The magnitude is only meaningful on the fast path.
+
+// Positive when a, b, c turn counterclockwise with y pointing up (so
+// clockwise on screen, where y points down), negative for the other way and
+// zero when they are on one line. Twice the signed area of the triangle.
+pub fn orient2d(a: Point, b: Point, c: Point) -> f64 {
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+    if det.abs() > ERRBOUND_A * (left.abs() + right.abs()) {
+	return det;
+    }
+    // the same determinant multiplied out so each term is a product of two
+    // coordinates (the c.x*c.y terms cancel)
+    let mut exact = Expansion::default();
+    exact.add_product(a.x, b.y);
+    exact.add_product(-a.x, c.y);
+    exact.add_product(-c.x, b.y);
+    exact.add_product(-a.y, b.x);
+    exact.add_product(a.y, c.x);
+    exact.add_product(c.y, b.x);
+    exact.sign()
+}
+
+// The sign of (b - a).(c - a): positive when c is ahead of a going towards b,
+// negative when it's behind, and zero when it's exactly on the line through a
+// perpendicular to ab.
+pub fn dot2d(a: Point, b: Point, c: Point) -> f64 {
+    let left = (b.x - a.x) * (c.x - a.x);
+    let right = (b.y - a.y) * (c.y - a.y);
+    let det = left + right;
+    if det.abs() > ERRBOUND_A * (left.abs() + right.abs()) {
+	return det;
+    }
+    let mut exact = Expansion::default();
+    for &(bv, cv, av) in [(b.x, c.x, a.x), (b.y, c.y, a.y)].iter() {
+	// (bv - av)(cv - av) = bv*cv - bv*av - av*cv + av*av
+	exact.add_product(bv, cv);
+	exact.add_product(-bv, av);
+	exact.add_product(-av, cv);
+	exact.add_product(av, av);
+    }
+    exact.sign()
+}
+
+// the sign of a*x + b*y + c for the line's own coefficients, worked out exactly
+pub fn line_side(line: Line, p: Point) -> f64 {
+    let mut exact = Expansion::default();
+    exact.add(line.c);
+    exact.add_product(line.a, p.x);
+    exact.add_product(line.b, p.y);
+    exact.sign()
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,149 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::tolerance::Tolerance;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Ray
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// `origin + t*dir` for t >= 0. dir doesn't need to be unit length, t is
+// measured in multiples of it.
+pub struct Ray {
+    pub origin: Point,
+    pub dir: Vector,
+}
+impl Ray {
+
+    pub fn at(&self, t: f64) -> Point {
+	self.origin.plus(self.dir.scaled(t))
+    }
+
+    // smallest t >= 0 where the ray hits the circle's boundary, or 0.0 if it
+    // starts inside
+    fn hit_disc(&self, center: Point, r: f64) -> Option<f64> {
+	let m = center.towards(self.origin);
+	let a = self.dir.dot(self.dir);
+	let b = m.dot(self.dir);
+	let c = m.dot(m) - r*r;
+	if c <= 0.0 {
+	    return Some(0.0);
+	}
+	if b > 0.0 || a == 0.0 {
+	    return None; // outside and pointing away
+	}
+	let disc = b*b - a*c;
+	if disc < 0.0 {
+	    return None;
+	}
+	Some((-b - disc.sqrt()) / a)
+    }
+
+    // t where the ray crosses the segment, if it does
+    fn hit_segment(&self, seg: Segment) -> Option<f64> {
+	let e = seg.p1.towards(seg.p2);
+	let denom = self.dir.cross(e);
+	if denom == 0.0 {
+	    return None; // parallel; the caps will catch grazing hits
+	}
+	let w = self.origin.towards(seg.p1);
+	let t = w.cross(e) / denom;
+	let u = w.cross(self.dir) / denom;
+	if t >= 0.0 && (0.0..=1.0).contains(&u) {
+	    Some(t)
+	} else {
+	    None
+	}
+    }
+
+    // the whole line the ray lies along
+    pub fn line(&self) -> ParametricLine {
+	ParametricLine { origin: self.origin, dir: self.dir }
+    }
+}
+
+// the half of the line from its origin on, the way dir points
+impl From<ParametricLine> for Ray {
+    fn from(l: ParametricLine) -> Ray {
+	Ray { origin: l.origin, dir: l.dir }
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Point and ray queries
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// A point is a circle of no radius, so a point on the boundary is contained
+// the same way touching shapes collide.
+impl Circle {
+
+    pub fn contains_point(&self, p: Point) -> bool {
+	self.contains_point_with(p, Tolerance::default())
+    }
+
+    pub fn contains_point_with(&self, p: Point, tolerance: Tolerance) -> bool {
+	tolerance.classify(self.p.distance(p) - self.r).is_touching()
+    }
+
+    pub fn raycast(&self, ray: Ray) -> Option<f64> {
+	ray.hit_disc(self.p, self.r)
+    }
+}
+
+impl Stad {
+
+    pub fn contains_point(&self, p: Point) -> bool {
+	self.contains_point_with(p, Tolerance::default())
+    }
+
+    pub fn contains_point_with(&self, p: Point, tolerance: Tolerance) -> bool {
+	tolerance.classify(self.segment().closest_point(p).distance(p) - self.r).is_touching()
+    }
+
+    // A stadium's boundary is its two end caps plus its two flat sides, so the
+    // first hit is the nearest of the hits on each of those.
+    pub fn raycast(&self, ray: Ray) -> Option<f64> {
+	if self.contains_point(ray.origin) {
+	    return Some(0.0);
+	}
+	let along = self.p1.towards(self.p2);
+	let len = along.magnitude();
+	let mut hits = vec![ray.hit_disc(self.p1, self.r), ray.hit_disc(self.p2, self.r)];
+	if len > 0.0 {
+	    let off = Vector{x: -along.y, y: along.x}.scaled(self.r/len);
+	    for side in [off, off.scaled(-1.0)].iter() {
+		hits.push(ray.hit_segment(Segment {
+		    p1: self.p1.plus(*side),
+		    p2: self.p2.plus(*side),
+		}));
+	    }
+	}
+	hits.into_iter().flatten().min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+impl Shape {
+
+    pub fn contains_point(&self, p: Point) -> bool {
+	self.contains_point_with(p, Tolerance::default())
+    }
+
+    pub fn contains_point_with(&self, p: Point, tolerance: Tolerance) -> bool {
+	match self {
+	    Shape::Circle(c) => c.contains_point_with(p, tolerance),
+	    Shape::Stad(s)   => s.contains_point_with(p, tolerance),
+	}
+    }
+
+    pub fn raycast(&self, ray: Ray) -> Option<f64> {
+	match self {
+	    Shape::Circle(c) => c.raycast(ray),
+	    Shape::Stad(s)   => s.raycast(ray),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
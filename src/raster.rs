@@ -0,0 +1,154 @@
+use crate::geometry::*;
+use crate::scene::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Image
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(PartialEq)]
+// 8 bit RGBA, row by row from the top left
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// how two images of the same size differ
+pub struct ImageDiff {
+    // the most any one channel of any one pixel is off by
+    pub max_channel: u8,
+    // how many pixels have some channel off by more than the threshold given
+    // to compare
+    pub differing: usize,
+}
+
+impl Image {
+
+    pub fn new(width: usize, height: usize, color: Color) -> Image {
+	Image { width, height, pixels: vec![to_bytes(color); width*height] }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> [u8; 4] {
+	self.pixels[y*self.width + x]
+    }
+
+    // None if the sizes differ
+    pub fn compare(&self, other: &Image, threshold: u8) -> Option<ImageDiff> {
+	if self.width != other.width || self.height != other.height {
+	    return None;
+	}
+	let mut diff = ImageDiff { max_channel: 0, differing: 0 };
+	for (a, b) in self.pixels.iter().zip(other.pixels.iter()) {
+	    let off = (0..4).map(|k| (a[k] as i32 - b[k] as i32).unsigned_abs() as u8).max().unwrap_or(0);
+	    diff.max_channel = diff.max_channel.max(off);
+	    if off > threshold {
+		diff.differing += 1;
+	    }
+	}
+	Some(diff)
+    }
+
+    // plain (not premultiplied) source-over, `coverage` of the way
+    fn blend(&mut self, x: usize, y: usize, color: Color, coverage: f64) {
+	let alpha = color.a as f64 * coverage;
+	let dst = &mut self.pixels[y*self.width + x];
+	let src = to_bytes(color);
+	for k in 0..3 {
+	    dst[k] = (src[k] as f64 * alpha + dst[k] as f64 * (1.0 - alpha)).round() as u8;
+	}
+	dst[3] = (255.0 * alpha + dst[3] as f64 * (1.0 - alpha)).round() as u8;
+    }
+}
+
+fn to_bytes(c: Color) -> [u8; 4] {
+    let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    [byte(c.r), byte(c.g), byte(c.b), byte(c.a)]
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Rasterizing
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Every primitive is some distance from a point or segment, so each is drawn
+// from its signed distance: negative inside, positive outside. A pixel is
+// covered by however much of it is inside, taking the edge as straight across
+// the pixel, which gives about the same antialiasing as the GPU does.
+
+// the signed distance from p to the primitive's edge
+fn signed_distance(primitive: Primitive, p: Point) -> f64 {
+    match primitive {
+	Primitive::Line { p1, p2, width, .. } => {
+	    // square ends: outside if past either end or too far to the side
+	    let along = p1.towards(p2);
+	    let len = along.magnitude();
+	    if len == 0.0 {
+		return f64::INFINITY;
+	    }
+	    let u = along.normalized();
+	    let t = p1.towards(p).dot(u);
+	    let side = p1.towards(p).cross(u).abs() - width/2.0;
+	    let end = (-t).max(t - len);
+	    side.max(end)
+	},
+	Primitive::Stad { p1, p2, r, .. } => {
+	    Segment { p1, p2 }.closest_point(p).distance(p) - r
+	},
+	Primitive::Disc { center, r, .. } => center.distance(p) - r,
+	Primitive::Ring { center, r, width, .. } => (center.distance(p) - r).abs() - width/2.0,
+    }
+}
+
+fn color_of(primitive: Primitive) -> Color {
+    match primitive {
+	Primitive::Line { color, .. } | Primitive::Stad { color, .. }
+	    | Primitive::Disc { color, .. } | Primitive::Ring { color, .. } => color,
+    }
+}
+
+// (min x, min y, max x, max y) of what the primitive could touch
+fn bounds(primitive: Primitive) -> (f64, f64, f64, f64) {
+    let around = |a: Point, b: Point, pad: f64| {
+	(a.x.min(b.x) - pad, a.y.min(b.y) - pad, a.x.max(b.x) + pad, a.y.max(b.y) + pad)
+    };
+    match primitive {
+	Primitive::Line { p1, p2, width, .. } => around(p1, p2, width),
+	Primitive::Stad { p1, p2, r, .. } => around(p1, p2, r),
+	Primitive::Disc { center, r, .. } => around(center, center, r),
+	Primitive::Ring { center, r, width, .. } => around(center, center, r + width),
+    }
+}
+
+// Draws the scene into a width by height image, with scene coordinates
+// multiplied by `scale` (so a 1200 wide demo fits in 600 pixels at 0.5).
+pub fn render(scene: &Scene, width: usize, height: usize, scale: f64) -> Image {
+    let mut image = Image::new(width, height, scene.background);
+    for &primitive in scene.primitives.iter() {
+	let color = color_of(primitive);
+	let (x0, y0, x1, y1) = bounds(primitive);
+	let pixel_range = |lo: f64, hi: f64, n: usize| {
+	    let lo = ((lo*scale - 1.0).floor().max(0.0) as usize).min(n);
+	    let hi = ((hi*scale + 1.0).ceil().max(0.0) as usize).min(n);
+	    lo..hi
+	};
+	for y in pixel_range(y0, y1, height) {
+	    for x in pixel_range(x0, x1, width) {
+		// the middle of the pixel, back in scene coordinates
+		let p = Point { x: (x as f64 + 0.5)/scale, y: (y as f64 + 0.5)/scale };
+		let coverage = (0.5 - signed_distance(primitive, p)*scale).clamp(0.0, 1.0);
+		if coverage > 0.0 {
+		    image.blend(x, y, color, coverage);
+		}
+	    }
+	}
+    }
+    image
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,121 @@
+#[cfg(feature = "ggez")]
+use ggez::{Context, GameResult};
+#[cfg(feature = "ggez")]
+use ggez::graphics::{self, DrawMode};
+
+use crate::geometry::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Scene
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// The same as ggez's Color (0 to 1, not premultiplied), kept here so a scene
+// can be built and rasterized without ggez.
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+impl Color {
+
+    pub const WHITE: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 };
+    pub const BLACK: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Color {
+	Color { r, g, b, a }
+    }
+}
+
+#[cfg(feature = "ggez")]
+impl From<Color> for graphics::Color {
+    fn from(c: Color) -> graphics::Color {
+	graphics::Color::new(c.r, c.g, c.b, c.a)
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// Everything the demos draw is one of these, so a scene can go to ggez or to
+// the CPU rasterizer in `raster` and come out looking the same.
+pub enum Primitive {
+    // a straight line `width` across with square ends (ggez's new_line)
+    Line { p1: Point, p2: Point, width: f64, color: Color },
+    // a stadium: a line 2r across with round ends
+    Stad { p1: Point, p2: Point, r: f64, color: Color },
+    Disc { center: Point, r: f64, color: Color },
+    // a circle outline `width` across, centered on radius r
+    Ring { center: Point, r: f64, width: f64, color: Color },
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(PartialEq)]
+// Drawn in order, on top of the background
+pub struct Scene {
+    pub background: Color,
+    pub primitives: Vec<Primitive>,
+}
+impl Scene {
+
+    pub fn new(background: Color) -> Scene {
+	Scene { background, primitives: vec![] }
+    }
+
+    pub fn line(&mut self, p1: Point, p2: Point, width: f64, color: Color) {
+	self.primitives.push(Primitive::Line { p1, p2, width, color });
+    }
+
+    pub fn stad(&mut self, s: Stad, color: Color) {
+	self.primitives.push(Primitive::Stad { p1: s.p1, p2: s.p2, r: s.r, color });
+    }
+
+    pub fn disc(&mut self, c: Circle, color: Color) {
+	self.primitives.push(Primitive::Disc { center: c.p, r: c.r, color });
+    }
+
+    pub fn ring(&mut self, center: Point, r: f64, width: f64, color: Color) {
+	self.primitives.push(Primitive::Ring { center, r, width, color });
+    }
+
+}
+
+#[cfg(feature = "ggez")]
+impl Scene {
+
+    // clears to the background and draws everything (but doesn't present)
+    pub fn draw(&self, ctx: &mut Context) -> GameResult<()> {
+	graphics::clear(ctx, self.background.into());
+	for p in self.primitives.iter() {
+	    let mesh = match *p {
+		Primitive::Line { p1, p2, width, color } => {
+		    graphics::Mesh::new_line(ctx, &[p1.mint(), p2.mint()], width as f32, color.into())?
+		},
+		Primitive::Stad { p1, p2, r, color } => {
+		    let stroke = graphics::StrokeOptions::default()
+			.with_line_width((r*2.0) as f32)
+			.with_start_cap(graphics::LineCap::Round)
+			.with_end_cap(graphics::LineCap::Round);
+		    graphics::Mesh::new_polyline(ctx, DrawMode::Stroke(stroke),
+						 &[p1.mint(), p2.mint()], color.into())?
+		},
+		Primitive::Disc { center, r, color } => {
+		    graphics::Mesh::new_circle(ctx, DrawMode::fill(), center.mint(), r as f32, 1.0, color.into())?
+		},
+		Primitive::Ring { center, r, width, color } => {
+		    graphics::Mesh::new_circle(ctx, DrawMode::stroke(width as f32), center.mint(),
+					       r as f32, 1.0, color.into())?
+		},
+	    };
+	    graphics::draw(ctx, &mesh, (Point{x: 0.0, y: 0.0}.mint(),))?;
+	}
+	Ok(())
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
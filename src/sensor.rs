@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+
+use crate::collider::*;
+use crate::broadphase;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SensorEvent
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum SensorEventKind {
+    // started overlapping this step
+    Enter,
+    // overlapped last step too
+    Stay,
+    // overlapped last step but not this one (or one of them is gone)
+    Exit,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+// `K` is whatever the colliders are known by: a Handle in a World, a body
+// index in a Simulation
+pub struct SensorEvent<K> {
+    pub kind: SensorEventKind,
+    pub sensor: K,
+    pub other: K,
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SensorTracker
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+// Remembers last step's (sensor, other) overlaps so this step's can be diffed
+// against them.
+pub struct SensorTracker<K: Ord + Copy> {
+    overlaps: BTreeSet<(K, K)>,
+}
+
+impl<K: Ord + Copy> Default for SensorTracker<K> {
+    fn default() -> SensorTracker<K> {
+	SensorTracker { overlaps: BTreeSet::new() }
+    }
+}
+
+impl<K: Ord + Copy> SensorTracker<K> {
+
+    pub fn new() -> SensorTracker<K> {
+	SensorTracker::default()
+    }
+
+    // Takes this step's (sensor, other) overlaps and gives an Enter or Stay
+    // for each, then an Exit for each of last step's that's gone. Each group
+    // comes out sorted so the order doesn't depend on the broadphase.
+    pub fn update<I>(&mut self, overlaps: I) -> Vec<SensorEvent<K>>
+    where I: IntoIterator<Item = (K, K)> {
+	let now: BTreeSet<(K, K)> = overlaps.into_iter().collect();
+	let event = |kind, &(sensor, other): &(K, K)| SensorEvent { kind, sensor, other };
+	let mut events: Vec<_> = now.iter().map(|pair| {
+	    let kind = if self.overlaps.contains(pair) {
+		SensorEventKind::Stay
+	    } else {
+		SensorEventKind::Enter
+	    };
+	    event(kind, pair)
+	}).collect();
+	events.extend(self.overlaps.difference(&now).map(|pair| event(SensorEventKind::Exit, pair)));
+	self.overlaps = now;
+	events
+    }
+
+    pub fn overlaps(&self) -> impl Iterator<Item = (K, K)> + '_ {
+	self.overlaps.iter().copied()
+    }
+
+    pub fn is_overlapping(&self, sensor: K, other: K) -> bool {
+	self.overlaps.contains(&(sensor, other))
+    }
+}
+
+// Every (sensor, other) pair that overlaps, by index into `colliders`. Two
+// sensors never report each other.
+pub fn sensor_overlaps(colliders: &[Collider]) -> Vec<(usize, usize)> {
+    broadphase::candidate_pairs(colliders).into_iter()
+	.filter(|&(a, b)| colliders[a].sensor != colliders[b].sensor)
+	.filter(|&(a, b)| colliders[a].shape.collides(colliders[b].shape))
+	.map(|(a, b)| if colliders[a].sensor { (a, b) } else { (b, a) })
+	.collect()
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
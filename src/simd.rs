@@ -0,0 +1,267 @@
+use crate::geometry::*;
+use crate::tolerance::DEFAULT_EPSILON;
+
+// Width of the kernel. On x86_64 (where SSE2 is always there) the kernel is
+// written with SSE2 intrinsics, two lanes to a register; elsewhere it falls
+// back to the same arithmetic on plain arrays, one lane at a time, which the
+// autovectorizer may or may not pack. `benches/primitives.rs` compares the
+// batch against calling `collides_stad` in a loop.
+pub const LANES: usize = 4;
+
+type Lane = [f64; LANES];
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// StadBatch
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Many stadiums stored structure-of-arrays so that LANES of them can be loaded
+// into registers at once.
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct StadBatch {
+    pub p1x: Vec<f64>,
+    pub p1y: Vec<f64>,
+    pub p2x: Vec<f64>,
+    pub p2y: Vec<f64>,
+    pub r: Vec<f64>,
+}
+impl StadBatch {
+
+    pub fn new() -> StadBatch {
+	StadBatch::default()
+    }
+
+    pub fn from_stads(stads: &[Stad]) -> StadBatch {
+	let mut batch = StadBatch::new();
+	for s in stads {
+	    batch.push(*s);
+	}
+	batch
+    }
+
+    pub fn push(&mut self, s: Stad) {
+	self.p1x.push(s.p1.x);
+	self.p1y.push(s.p1.y);
+	self.p2x.push(s.p2.x);
+	self.p2y.push(s.p2.y);
+	self.r.push(s.r);
+    }
+
+    pub fn len(&self) -> usize {
+	self.r.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.r.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> Stad {
+	Stad {
+	    p1: Point{x: self.p1x[i], y: self.p1y[i]},
+	    p2: Point{x: self.p2x[i], y: self.p2y[i]},
+	    r: self.r[i],
+	}
+    }
+
+    // Runs `kernel` over every group of LANES stadiums. The last group is
+    // padded out with copies of the final stadium and the padding's results
+    // are dropped.
+    #[allow(clippy::needless_range_loop)]
+    fn for_each_lane<F>(&self, mut kernel: F)
+    where F: FnMut(usize, &Lane, &Lane, &Lane, &Lane, &Lane) {
+	let n = self.len();
+	let mut start = 0;
+	while start < n {
+	    let mut lanes = [[0.0; LANES]; 5];
+	    for k in 0..LANES {
+		let i = (start + k).min(n - 1);
+		lanes[0][k] = self.p1x[i];
+		lanes[1][k] = self.p1y[i];
+		lanes[2][k] = self.p2x[i];
+		lanes[3][k] = self.p2y[i];
+		lanes[4][k] = self.r[i];
+	    }
+	    kernel(start, &lanes[0], &lanes[1], &lanes[2], &lanes[3], &lanes[4]);
+	    start += LANES;
+	}
+    }
+
+    // The squared distance between the core segment of `s` and that of every
+    // stadium in the batch, written to `out` in batch order
+    pub fn segment_distances_squared(&self, s: Stad, out: &mut Vec<f64>) {
+	out.clear();
+	let n = self.len();
+	self.for_each_lane(|start, p1x, p1y, p2x, p2y, _| {
+	    let d2 = segment_distance_squared_lanes(s.segment(), p1x, p1y, p2x, p2y);
+	    let take = LANES.min(n - start);
+	    out.extend_from_slice(&d2[..take]);
+	});
+    }
+
+    // The test `s.collides_stad(self.get(i))` makes for every i, but on squared
+    // distances, so the two can differ for pairs within rounding of touching
+    // (tests/simd_batch.rs checks that they agree otherwise)
+    pub fn collides_stad(&self, s: Stad) -> Vec<bool> {
+	let n = self.len();
+	let mut hits = Vec::with_capacity(n);
+	self.for_each_lane(|start, p1x, p1y, p2x, p2y, r| {
+	    let d2 = segment_distance_squared_lanes(s.segment(), p1x, p1y, p2x, p2y);
+	    let take = LANES.min(n - start);
+	    for k in 0..take {
+		// touching within the tolerance counts, as in the scalar test
+		let reach = s.r + r[k] + DEFAULT_EPSILON;
+		hits.push(d2[k] <= reach*reach);
+	    }
+	});
+	hits
+    }
+
+    // Every (i, j) such that self.get(i) collides with other.get(j), sorted
+    pub fn colliding_pairs(&self, other: &StadBatch) -> Vec<(usize, usize)> {
+	let mut pairs = vec![];
+	for i in 0..self.len() {
+	    for (j, hit) in other.collides_stad(self.get(i)).into_iter().enumerate() {
+		if hit {
+		    pairs.push((i, j));
+		}
+	    }
+	}
+	pairs
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Kernel
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Branch free segment to segment distance: if the segments cross it's zero,
+// otherwise it's the nearest of the four endpoint to segment distances. Both
+// versions do the same operations in the same order, so they give the same
+// bits.
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+use sse2::segment_distance_squared_lanes;
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+use portable::segment_distance_squared_lanes;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod sse2 {
+    use std::arch::x86_64::*;
+
+    use crate::geometry::*;
+    use super::{Lane, LANES};
+
+    // squared distance from (px, py) to the segment a->a+d, with dd = |d|^2.
+    // Where dd is zero the division gives garbage, which the mask replaces
+    // with t = 0.
+    #[inline(always)]
+    unsafe fn point_segment_distance_squared(px: __m128d, py: __m128d, ax: __m128d, ay: __m128d,
+					     dx: __m128d, dy: __m128d, dd: __m128d) -> __m128d {
+	let (zero, one) = (_mm_setzero_pd(), _mm_set1_pd(1.0));
+	let t = _mm_div_pd(_mm_add_pd(_mm_mul_pd(_mm_sub_pd(px, ax), dx),
+				      _mm_mul_pd(_mm_sub_pd(py, ay), dy)), dd);
+	let t = _mm_and_pd(_mm_cmpgt_pd(dd, zero), _mm_max_pd(_mm_min_pd(t, one), zero));
+	let ex = _mm_sub_pd(_mm_add_pd(ax, _mm_mul_pd(dx, t)), px);
+	let ey = _mm_sub_pd(_mm_add_pd(ay, _mm_mul_pd(dy, t)), py);
+	_mm_add_pd(_mm_mul_pd(ex, ex), _mm_mul_pd(ey, ey))
+    }
+
+    // a x b for the 2d cross product, lane by lane
+    #[inline(always)]
+    unsafe fn cross(ax: __m128d, ay: __m128d, bx: __m128d, by: __m128d) -> __m128d {
+	_mm_sub_pd(_mm_mul_pd(ax, by), _mm_mul_pd(ay, bx))
+    }
+
+    #[inline(always)]
+    pub(super) fn segment_distance_squared_lanes(seg: Segment, p1x: &Lane, p1y: &Lane,
+						 p2x: &Lane, p2y: &Lane) -> Lane {
+	let mut out = [0.0; LANES];
+	// SAFETY: SSE2 is enabled for the whole build (the cfg on this module),
+	// and every load and store is an unaligned one of two f64s that starts
+	// at an even index below LANES
+	unsafe {
+	    let (ax, ay) = (_mm_set1_pd(seg.p1.x), _mm_set1_pd(seg.p1.y));
+	    let (a2x, a2y) = (_mm_set1_pd(seg.p2.x), _mm_set1_pd(seg.p2.y));
+	    let (adx, ady) = (_mm_sub_pd(a2x, ax), _mm_sub_pd(a2y, ay));
+	    let add = _mm_add_pd(_mm_mul_pd(adx, adx), _mm_mul_pd(ady, ady));
+	    let zero = _mm_setzero_pd();
+
+	    for k in (0..LANES).step_by(2) {
+		let (bx, by) = (_mm_loadu_pd(p1x[k..].as_ptr()), _mm_loadu_pd(p1y[k..].as_ptr()));
+		let (b2x, b2y) = (_mm_loadu_pd(p2x[k..].as_ptr()), _mm_loadu_pd(p2y[k..].as_ptr()));
+		let (bdx, bdy) = (_mm_sub_pd(b2x, bx), _mm_sub_pd(b2y, by));
+		let bdd = _mm_add_pd(_mm_mul_pd(bdx, bdx), _mm_mul_pd(bdy, bdy));
+
+		let d1 = point_segment_distance_squared(bx, by, ax, ay, adx, ady, add);
+		let d2 = point_segment_distance_squared(b2x, b2y, ax, ay, adx, ady, add);
+		let d3 = point_segment_distance_squared(ax, ay, bx, by, bdx, bdy, bdd);
+		let d4 = point_segment_distance_squared(a2x, a2y, bx, by, bdx, bdy, bdd);
+		let nearest = _mm_min_pd(_mm_min_pd(d1, d2), _mm_min_pd(d3, d4));
+
+		// which side of each segment the other's endpoints are on
+		let o1 = cross(adx, ady, _mm_sub_pd(bx, ax), _mm_sub_pd(by, ay));
+		let o2 = cross(adx, ady, _mm_sub_pd(b2x, ax), _mm_sub_pd(b2y, ay));
+		let o3 = cross(bdx, bdy, _mm_sub_pd(ax, bx), _mm_sub_pd(ay, by));
+		let o4 = cross(bdx, bdy, _mm_sub_pd(a2x, bx), _mm_sub_pd(a2y, by));
+		let crossing = _mm_and_pd(_mm_cmplt_pd(_mm_mul_pd(o1, o2), zero),
+					  _mm_cmplt_pd(_mm_mul_pd(o3, o4), zero));
+
+		_mm_storeu_pd(out[k..].as_mut_ptr(), _mm_andnot_pd(crossing, nearest));
+	    }
+	}
+	out
+    }
+}
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+mod portable {
+    use crate::geometry::*;
+    use super::{Lane, LANES};
+
+    // squared distance from (px, py) to the segment a->a+d, with dd = |d|^2
+    #[inline(always)]
+    fn point_segment_distance_squared(px: f64, py: f64, ax: f64, ay: f64,
+				      dx: f64, dy: f64, dd: f64) -> f64 {
+	let t = ((px - ax)*dx + (py - ay)*dy) / dd;
+	let t = if dd > 0.0 { t.clamp(0.0, 1.0) } else { 0.0 };
+	let ex = ax + dx*t - px;
+	let ey = ay + dy*t - py;
+	ex*ex + ey*ey
+    }
+
+    #[inline(always)]
+    pub(super) fn segment_distance_squared_lanes(seg: Segment, p1x: &Lane, p1y: &Lane,
+						 p2x: &Lane, p2y: &Lane) -> Lane {
+	let (ax, ay) = (seg.p1.x, seg.p1.y);
+	let (adx, ady) = (seg.p2.x - ax, seg.p2.y - ay);
+	let add = adx*adx + ady*ady;
+
+	let mut out = [0.0; LANES];
+	for k in 0..LANES {
+	    let (bx, by) = (p1x[k], p1y[k]);
+	    let (bdx, bdy) = (p2x[k] - bx, p2y[k] - by);
+	    let bdd = bdx*bdx + bdy*bdy;
+
+	    let d1 = point_segment_distance_squared(bx, by, ax, ay, adx, ady, add);
+	    let d2 = point_segment_distance_squared(p2x[k], p2y[k], ax, ay, adx, ady, add);
+	    let d3 = point_segment_distance_squared(ax, ay, bx, by, bdx, bdy, bdd);
+	    let d4 = point_segment_distance_squared(seg.p2.x, seg.p2.y, bx, by, bdx, bdy, bdd);
+	    let nearest = d1.min(d2).min(d3.min(d4));
+
+	    // which side of each segment the other's endpoints are on
+	    let o1 = adx*(by - ay) - ady*(bx - ax);
+	    let o2 = adx*(p2y[k] - ay) - ady*(p2x[k] - ax);
+	    let o3 = bdx*(ay - by) - bdy*(ax - bx);
+	    let o4 = bdx*(seg.p2.y - by) - bdy*(seg.p2.x - bx);
+	    let crossing = o1*o2 < 0.0 && o3*o4 < 0.0;
+
+	    out[k] = if crossing { 0.0 } else { nearest };
+	}
+	out
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,345 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::broadphase;
+use crate::static_geometry::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SoftChain
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// Position based: a particle's velocity is where it is minus where it was last
+// step, so moving a particle to fix a constraint also changes its velocity.
+pub struct Particle {
+    pub position: Point,
+    pub previous: Point,
+    // zero pins the particle in place
+    pub inv_mass: f64,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// keeps particles a and b `rest` apart
+pub struct Link {
+    pub a: usize,
+    pub b: usize,
+    pub rest: f64,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+// A row of particles with a stadium of radius `radius` between each
+// neighbouring pair. A closed chain also joins the last particle to the first
+// and tries to keep its area, which makes it a squishy blob rather than a
+// loose loop of rope.
+pub struct SoftChain {
+    pub particles: Vec<Particle>,
+    pub links: Vec<Link>,
+    pub radius: f64,
+    pub closed: bool,
+    // 0 to 1, how much of each link's error is fixed per iteration
+    pub stiffness: f64,
+    // the area a closed chain keeps, scaled by `pressure`
+    pub rest_area: f64,
+    pub pressure: f64,
+}
+impl SoftChain {
+
+    // an open chain through `points`, links along it only
+    pub fn rope(points: &[Point], radius: f64) -> SoftChain {
+	let mut chain = SoftChain::from_points(points, radius, false);
+	chain.stiffness = 1.0;
+	chain
+    }
+
+    // A closed ring of n particles around `center`. Links that skip a particle
+    // stop the ring from folding up on itself.
+    pub fn blob(center: Point, size: f64, n: usize, radius: f64) -> SoftChain {
+	let points: Vec<_> = (0..n).map(|i| {
+	    let angle = std::f64::consts::PI * 2.0 * i as f64 / n as f64;
+	    center.plus(Vector{x: size, y: 0.0}.rotated(angle))
+	}).collect();
+	let mut chain = SoftChain::from_points(&points, radius, true);
+	for i in 0..n {
+	    chain.link(i, (i + 2) % n);
+	}
+	chain.rest_area = chain.area();
+	chain
+    }
+
+    fn from_points(points: &[Point], radius: f64, closed: bool) -> SoftChain {
+	let mut chain = SoftChain {
+	    particles: points.iter().map(|&p| Particle {
+		position: p,
+		previous: p,
+		inv_mass: 1.0,
+	    }).collect(),
+	    links: vec![],
+	    radius,
+	    closed,
+	    stiffness: 0.5,
+	    rest_area: 0.0,
+	    pressure: 1.0,
+	};
+	for i in 0..chain.segment_count() {
+	    chain.link(i, (i + 1) % points.len());
+	}
+	chain
+    }
+
+    // links a and b at their current distance
+    pub fn link(&mut self, a: usize, b: usize) {
+	let rest = self.particles[a].position.distance(self.particles[b].position);
+	self.links.push(Link { a, b, rest });
+    }
+
+    pub fn segment_count(&self) -> usize {
+	match self.particles.len() {
+	    0 | 1 => 0,
+	    n if self.closed => n,
+	    n => n - 1,
+	}
+    }
+
+    // the particles at either end of segment i
+    pub fn segment_ends(&self, i: usize) -> (usize, usize) {
+	(i, (i + 1) % self.particles.len())
+    }
+
+    pub fn segment(&self, i: usize) -> Stad {
+	let (a, b) = self.segment_ends(i);
+	Stad {
+	    p1: self.particles[a].position,
+	    p2: self.particles[b].position,
+	    r: self.radius,
+	}
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = Stad> + '_ {
+	(0..self.segment_count()).map(move |i| self.segment(i))
+    }
+
+    // signed area enclosed by the particles (positive when they go clockwise
+    // on screen, with y pointing down)
+    pub fn area(&self) -> f64 {
+	let n = self.particles.len();
+	(0..n).map(|i| {
+	    let p = self.particles[i].position;
+	    let q = self.particles[(i + 1) % n].position;
+	    p.x*q.y - q.x*p.y
+	}).sum::<f64>() / 2.0
+    }
+
+    fn solve_links(&mut self) {
+	for link in self.links.iter() {
+	    let (pa, pb) = (self.particles[link.a], self.particles[link.b]);
+	    let w = pa.inv_mass + pb.inv_mass;
+	    let d = pa.position.towards(pb.position);
+	    let dist = d.magnitude();
+	    if w == 0.0 || dist == 0.0 {
+		continue;
+	    }
+	    let correction = d.scaled(self.stiffness * (dist - link.rest) / dist / w);
+	    self.particles[link.a].position = pa.position.plus(correction.scaled(pa.inv_mass));
+	    self.particles[link.b].position = pb.position.minus(correction.scaled(pb.inv_mass));
+	}
+    }
+
+    // pushes every particle out (or pulls it in) along the gradient of the
+    // area until the area is back to the rest area
+    fn solve_area(&mut self) {
+	if !self.closed || self.particles.len() < 3 {
+	    return;
+	}
+	let n = self.particles.len();
+	let gradient = |ps: &[Particle], i: usize| {
+	    let prev = ps[(i + n - 1) % n].position;
+	    let next = ps[(i + 1) % n].position;
+	    Vector{x: next.y - prev.y, y: prev.x - next.x}.scaled(0.5)
+	};
+	let error = self.area() - self.rest_area * self.pressure;
+	let mut sum = 0.0;
+	for i in 0..n {
+	    let g = gradient(&self.particles, i);
+	    sum += self.particles[i].inv_mass * g.dot(g);
+	}
+	if sum == 0.0 {
+	    return;
+	}
+	let lambda = self.stiffness * error / sum;
+	let moves: Vec<_> = (0..n).map(|i| {
+	    gradient(&self.particles, i).scaled(-lambda * self.particles[i].inv_mass)
+	}).collect();
+	for (particle, v) in self.particles.iter_mut().zip(moves) {
+	    particle.position = particle.position.plus(v);
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SoftSimulation
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// where along segment (p1, p2) its closest point to q is, 0 at p1 and 1 at p2
+fn param(p1: Point, p2: Point, q: Point) -> f64 {
+    let d = p1.towards(p2);
+    let len2 = d.dot(d);
+    if len2 == 0.0 {
+	return 0.5;
+    }
+    (p1.towards(q).dot(d) / len2).clamp(0.0, 1.0)
+}
+
+// Moves the two ends of a segment so the point at `t` along it moves by about
+// `v`, sharing the move out by how near each end is and by inverse mass.
+// Returns how much of `v` was taken up (0 if both ends are pinned).
+fn push_segment(a: &mut Particle, b: &mut Particle, t: f64, v: Vector, others: f64) -> f64 {
+    let (wa, wb) = ((1.0 - t) * a.inv_mass, t * b.inv_mass);
+    let k = (1.0 - t)*wa + t*wb;
+    if k == 0.0 {
+	return 0.0;
+    }
+    let lambda = 1.0 / (k + others);
+    a.position = a.position.plus(v.scaled(lambda * wa));
+    b.position = b.position.plus(v.scaled(lambda * wb));
+    k * lambda
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+// Steps soft chains with position based dynamics: move every particle by its
+// velocity and gravity, then fix up links, areas and collisions by moving
+// particles directly, a few times over. Collisions use the ordinary stadium
+// contacts between each chain segment and the level or other chains'
+// segments. A chain doesn't collide with itself.
+pub struct SoftSimulation {
+    pub chains: Vec<SoftChain>,
+    pub gravity: Vector,
+    pub iterations: usize,
+    // fraction of velocity kept each step
+    pub damping: f64,
+    // fraction of sliding motion along the level removed each step
+    pub friction: f64,
+}
+impl SoftSimulation {
+
+    pub fn new(gravity: Vector) -> SoftSimulation {
+	SoftSimulation {
+	    chains: vec![],
+	    gravity,
+	    iterations: 8,
+	    damping: 0.99,
+	    friction: 0.3,
+	}
+    }
+
+    pub fn add_chain(&mut self, chain: SoftChain) -> usize {
+	self.chains.push(chain);
+	self.chains.len() - 1
+    }
+
+    pub fn step(&mut self, level: &StaticGeometry, dt: f64) {
+	let fall = self.gravity.scaled(dt*dt);
+	for particle in self.chains.iter_mut().flat_map(|c| c.particles.iter_mut()) {
+	    if particle.inv_mass == 0.0 {
+		particle.previous = particle.position;
+		continue;
+	    }
+	    let v = particle.previous.towards(particle.position).scaled(self.damping);
+	    particle.previous = particle.position;
+	    particle.position = particle.position.plus(v).plus(fall);
+	}
+
+	for _ in 0..self.iterations {
+	    for chain in self.chains.iter_mut() {
+		chain.solve_links();
+		chain.solve_area();
+	    }
+	    self.collide_chains();
+	    self.collide_level(level, false);
+	}
+	self.collide_level(level, true);
+    }
+
+    // every chain segment against the level, which doesn't move
+    fn collide_level(&mut self, level: &StaticGeometry, with_friction: bool) {
+	let friction = self.friction;
+	for chain in self.chains.iter_mut() {
+	    for i in 0..chain.segment_count() {
+		let (a, b) = chain.segment_ends(i);
+		let collider = Collider::stad(chain.segment(i));
+		for (_, contact) in level.contacts(&collider) {
+		    let s = chain.segment(i);
+		    let t = param(s.p1, s.p2, contact.point);
+		    let (pa, pb) = pair_mut(&mut chain.particles, a, b);
+		    if with_friction {
+			// take some of the sliding out of the ends' velocities
+			for p in [pa, pb] {
+			    let v = p.previous.towards(p.position);
+			    let along = contact.normal.perpendicular();
+			    let slide = along.scaled(v.dot(along) * friction);
+			    p.previous = p.previous.plus(slide);
+			}
+		    } else {
+			push_segment(pa, pb, t, contact.normal.scaled(-contact.depth), 0.0);
+		    }
+		}
+	    }
+	}
+    }
+
+    // segments of different chains against each other, using the broadphase
+    fn collide_chains(&mut self) {
+	let mut owners = vec![];
+	let mut colliders = vec![];
+	for (c, chain) in self.chains.iter().enumerate() {
+	    for i in 0..chain.segment_count() {
+		owners.push((c, i));
+		colliders.push(Collider::stad(chain.segment(i)));
+	    }
+	}
+	for (x, y) in broadphase::candidate_pairs(&colliders) {
+	    let ((ca, sa), (cb, sb)) = (owners[x], owners[y]);
+	    if ca == cb {
+		continue;
+	    }
+	    let (segment_a, segment_b) = (self.chains[ca].segment(sa), self.chains[cb].segment(sb));
+	    let contact = match segment_a.contact_stad(segment_b) {
+		Some(contact) => contact,
+		None => continue,
+	    };
+	    let ta = param(segment_a.p1, segment_a.p2, contact.point);
+	    let tb = param(segment_b.p1, segment_b.p2, contact.point);
+	    let weight = |chain: &SoftChain, s: usize, t: f64| {
+		let (a, b) = chain.segment_ends(s);
+		(1.0 - t).powi(2)*chain.particles[a].inv_mass + t.powi(2)*chain.particles[b].inv_mass
+	    };
+	    let (ka, kb) = (weight(&self.chains[ca], sa, ta), weight(&self.chains[cb], sb, tb));
+	    // each side moves its share of the overlap, as with two bodies
+	    let push = contact.normal.scaled(contact.depth);
+	    let (a1, a2) = self.chains[ca].segment_ends(sa);
+	    let (p1, p2) = pair_mut(&mut self.chains[ca].particles, a1, a2);
+	    push_segment(p1, p2, ta, push.scaled(-1.0), kb);
+	    let (b1, b2) = self.chains[cb].segment_ends(sb);
+	    let (p1, p2) = pair_mut(&mut self.chains[cb].particles, b1, b2);
+	    push_segment(p1, p2, tb, push, ka);
+	}
+    }
+}
+
+// two different elements of a slice, both mutably
+fn pair_mut<T>(v: &mut [T], a: usize, b: usize) -> (&mut T, &mut T) {
+    if a < b {
+	let (lo, hi) = v.split_at_mut(b);
+	(&mut lo[a], &mut hi[0])
+    } else {
+	let (lo, hi) = v.split_at_mut(a);
+	(&mut hi[0], &mut lo[b])
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::geometry::*;
+use crate::body::*;
+use crate::dynamics::BodyContact;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SolverSettings
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct SolverSettings {
+    pub iterations: usize,
+    // Baumgarte factor: the fraction of the overlap (beyond `slop`) turned into
+    // separating velocity each step
+    pub baumgarte: f64,
+    pub slop: f64,
+    // closing speeds below this don't bounce, so resting contacts stay resting
+    pub restitution_threshold: f64,
+    pub warm_starting: bool,
+}
+
+impl Default for SolverSettings {
+    fn default() -> SolverSettings {
+	SolverSettings {
+	    iterations: 10,
+	    baumgarte: 0.2,
+	    slop: 0.5,
+	    restitution_threshold: 30.0,
+	    warm_starting: true,
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// ContactSolver
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+struct Constraint {
+    a: usize,
+    b: usize,
+    id: u32,
+    ra: Vector,
+    rb: Vector,
+    normal: Vector,
+    tangent: Vector,
+    normal_mass: f64,
+    tangent_mass: f64,
+    friction: f64,
+    bias: f64,
+    normal_impulse: f64,
+    tangent_impulse: f64,
+}
+
+// Sequential impulses: each contact is solved on its own in turn, over and
+// over, and the accumulated impulses converge on a solution for all of them.
+// Accumulated impulses are remembered per contact (a pair of bodies and a
+// manifold point id) and used as the starting guess on the next step (warm
+// starting), which is what lets stacks settle.
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct ContactSolver {
+    pub settings: SolverSettings,
+    cache: HashMap<(usize, usize, u32), (f64, f64)>,
+    constraints: Vec<Constraint>,
+}
+impl ContactSolver {
+
+    pub fn new(settings: SolverSettings) -> ContactSolver {
+	ContactSolver {
+	    settings,
+	    cache: HashMap::new(),
+	    constraints: vec![],
+	}
+    }
+
+    // Adjusts the bodies' velocities so the contacts stop closing (and, via the
+    // bias, start pushing overlapping bodies apart). Positions aren't touched.
+    pub fn solve(&mut self, bodies: &mut [RigidBody], contacts: &[BodyContact], dt: f64) {
+	self.begin(bodies, contacts, dt);
+	for _ in 0..self.settings.iterations {
+	    self.iterate(bodies);
+	}
+	self.end();
+    }
+
+    // `solve` in pieces, so other constraints (joints) can take turns with
+    // the contacts inside the same iterations. Sets up this step's contacts
+    // and applies last step's impulses to them.
+    pub fn begin(&mut self, bodies: &mut [RigidBody], contacts: &[BodyContact], dt: f64) {
+	self.constraints = contacts.iter().map(|c| self.prepare(bodies, c, dt)).collect();
+
+	if self.settings.warm_starting {
+	    for c in self.constraints.iter() {
+		apply(bodies, c, c.normal.scaled(c.normal_impulse)
+		      .plus(c.tangent.scaled(c.tangent_impulse)));
+	    }
+	}
+    }
+
+    // one pass over every contact
+    pub fn iterate(&mut self, bodies: &mut [RigidBody]) {
+	for c in self.constraints.iter_mut() {
+	    // friction first so the normal impulse, which matters more, gets the last word
+	    let vt = relative_velocity(bodies, c).dot(c.tangent);
+	    let limit = c.friction * c.normal_impulse;
+	    let old = c.tangent_impulse;
+	    c.tangent_impulse = (old - c.tangent_mass*vt).clamp(-limit, limit);
+	    apply(bodies, c, c.tangent.scaled(c.tangent_impulse - old));
+
+	    let vn = relative_velocity(bodies, c).dot(c.normal);
+	    let old = c.normal_impulse;
+	    c.normal_impulse = (old + c.normal_mass*(c.bias - vn)).max(0.0);
+	    apply(bodies, c, c.normal.scaled(c.normal_impulse - old));
+	}
+    }
+
+    // remembers the impulses for warm starting the next step
+    pub fn end(&mut self) {
+	self.cache = self.constraints.drain(..)
+	    .map(|c| ((c.a, c.b, c.id), (c.normal_impulse, c.tangent_impulse)))
+	    .collect();
+    }
+
+    fn prepare(&self, bodies: &[RigidBody], c: &BodyContact, dt: f64) -> Constraint {
+	let (a, b) = (&bodies[c.a], &bodies[c.b]);
+	let p = c.contact.point;
+	let normal = c.contact.normal;
+	let tangent = normal.perpendicular();
+	let ra = a.position.towards(p);
+	let rb = b.position.towards(p);
+
+	let effective_mass = |dir: Vector| {
+	    let ran = ra.cross(dir);
+	    let rbn = rb.cross(dir);
+	    let k = a.inv_mass + b.inv_mass + ran*ran*a.inv_inertia + rbn*rbn*b.inv_inertia;
+	    if k > 0.0 { 1.0/k } else { 0.0 }
+	};
+
+	let closing = b.velocity_at(p).minus(a.velocity_at(p)).dot(normal);
+	let mut bias = self.settings.baumgarte / dt * (c.contact.depth - self.settings.slop).max(0.0);
+	if closing < -self.settings.restitution_threshold {
+	    bias = bias.max(-a.restitution.max(b.restitution) * closing);
+	}
+
+	let (normal_impulse, tangent_impulse) = if self.settings.warm_starting {
+	    self.cache.get(&(c.a, c.b, c.id)).copied().unwrap_or((0.0, 0.0))
+	} else {
+	    (0.0, 0.0)
+	};
+
+	Constraint {
+	    a: c.a,
+	    b: c.b,
+	    id: c.id,
+	    ra,
+	    rb,
+	    normal,
+	    tangent,
+	    normal_mass: effective_mass(normal),
+	    tangent_mass: effective_mass(tangent),
+	    friction: (a.friction * b.friction).sqrt(),
+	    bias,
+	    normal_impulse,
+	    tangent_impulse,
+	}
+    }
+}
+
+fn relative_velocity(bodies: &[RigidBody], c: &Constraint) -> Vector {
+    let (a, b) = (&bodies[c.a], &bodies[c.b]);
+    let va = a.velocity.plus(c.ra.perpendicular().scaled(a.angular_velocity));
+    let vb = b.velocity.plus(c.rb.perpendicular().scaled(b.angular_velocity));
+    vb.minus(va)
+}
+
+// impulse pushes b along it and a against it
+fn apply(bodies: &mut [RigidBody], c: &Constraint, impulse: Vector) {
+    let p = bodies[c.a].position.plus(c.ra);
+    bodies[c.a].apply_impulse(impulse.scaled(-1.0), p);
+    bodies[c.b].apply_impulse(impulse, p);
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,180 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::broadphase::Aabb;
+use crate::contact::Contact;
+use crate::query::Ray;
+
+// a node with more walls than this gets split, unless it is already this deep
+const LEAF_SIZE: usize = 8;
+const MAX_DEPTH: u32 = 12;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// StaticGeometry
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// A node's bounds aren't stored, they're recomputed on the way down from the
+// root, and its walls are a contiguous run of `items`. The four children of a
+// node sit next to each other in `nodes` so one index finds all of them.
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+struct Node {
+    children: u32, // index of the first child, 0 for a leaf (the root is never a child)
+    start: u32,
+    len: u32,
+}
+
+// Level geometry that never moves: a quadtree bulk loaded once from a list of
+// stadiums. Each wall lives in the smallest quadrant that fully contains it.
+// Use the broadphase / World for anything that moves.
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct StaticGeometry {
+    walls: Vec<Stad>,
+    boxes: Vec<Aabb>,
+    bounds: Aabb,
+    nodes: Vec<Node>,
+    items: Vec<u32>,
+    pub filter: CollisionFilter,
+}
+impl StaticGeometry {
+
+    pub fn new(walls: &[Stad], filter: CollisionFilter) -> StaticGeometry {
+	let boxes: Vec<Aabb> = walls.iter().map(|s| s.aabb()).collect();
+	let mut bounds = Aabb {
+	    min: Point{x: 0.0, y: 0.0},
+	    max: Point{x: 0.0, y: 0.0},
+	};
+	if let Some(first) = boxes.first() {
+	    bounds = *first;
+	    for b in boxes.iter() {
+		bounds = bounds.union(*b);
+	    }
+	}
+	let mut geo = StaticGeometry {
+	    walls: walls.to_vec(),
+	    boxes,
+	    bounds,
+	    nodes: vec![Node { children: 0, start: 0, len: 0 }],
+	    items: Vec::with_capacity(walls.len()),
+	    filter,
+	};
+	let all: Vec<u32> = (0..walls.len() as u32).collect();
+	geo.build(0, bounds, all, 0);
+	geo
+    }
+
+    fn build(&mut self, node: usize, bounds: Aabb, walls: Vec<u32>, depth: u32) {
+	let mut here = vec![];
+	let mut below: [Vec<u32>; 4] = Default::default();
+	if walls.len() > LEAF_SIZE && depth < MAX_DEPTH {
+	    for w in walls {
+		match (0..4).find(|&q| bounds.quadrant(q).contains(self.boxes[w as usize])) {
+		    Some(q) => below[q].push(w),
+		    None => here.push(w),
+		}
+	    }
+	} else {
+	    here = walls;
+	}
+
+	self.nodes[node].start = self.items.len() as u32;
+	self.nodes[node].len = here.len() as u32;
+	self.items.extend(here);
+
+	if below.iter().all(|b| b.is_empty()) {
+	    return;
+	}
+	let first = self.nodes.len();
+	self.nodes[node].children = first as u32;
+	for _ in 0..4 {
+	    self.nodes.push(Node { children: 0, start: 0, len: 0 });
+	}
+	for (q, walls) in below.iter_mut().enumerate() {
+	    let walls = std::mem::take(walls);
+	    self.build(first + q, bounds.quadrant(q), walls, depth + 1);
+	}
+    }
+
+    pub fn len(&self) -> usize {
+	self.walls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.walls.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> Stad {
+	self.walls[i]
+    }
+
+    pub fn walls(&self) -> &[Stad] {
+	&self.walls
+    }
+
+    // indices of every wall whose box overlaps `area`, appended to `out`
+    pub fn query_aabb(&self, area: Aabb, out: &mut Vec<usize>) {
+	let mut stack = vec![(0usize, self.bounds)];
+	while let Some((n, bounds)) = stack.pop() {
+	    let node = self.nodes[n];
+	    let items = &self.items[node.start as usize..(node.start + node.len) as usize];
+	    for &w in items {
+		if self.boxes[w as usize].overlaps(area) {
+		    out.push(w as usize);
+		}
+	    }
+	    if node.children != 0 {
+		for q in 0..4 {
+		    let child = bounds.quadrant(q);
+		    if child.overlaps(area) {
+			stack.push((node.children as usize + q, child));
+		    }
+		}
+	    }
+	}
+    }
+
+    // walls touching the collider, sorted, if its filter and ours allow it
+    pub fn overlapping(&self, collider: &Collider) -> Vec<usize> {
+	let mut hits = vec![];
+	if !collider.filter.allows(self.filter) {
+	    return hits;
+	}
+	self.query_aabb(collider.shape.aabb(), &mut hits);
+	hits.retain(|&w| collider.shape.collides(Shape::Stad(self.walls[w])));
+	hits.sort_unstable();
+	hits
+    }
+
+    // contacts from the collider towards each wall it touches, sorted by wall
+    pub fn contacts(&self, collider: &Collider) -> Vec<(usize, Contact)> {
+	if !collider.filter.allows(self.filter) {
+	    return vec![];
+	}
+	let mut candidates = vec![];
+	self.query_aabb(collider.shape.aabb(), &mut candidates);
+	candidates.sort_unstable();
+	candidates.into_iter()
+	    .filter_map(|w| collider.shape.contact(Shape::Stad(self.walls[w])).map(|c| (w, c)))
+	    .collect()
+    }
+
+    // the first wall along the ray within max_t, as (wall, t)
+    pub fn raycast(&self, ray: Ray, max_t: f64, filter: CollisionFilter) -> Option<(usize, f64)> {
+	if !filter.allows(self.filter) {
+	    return None;
+	}
+	let (a, b) = (ray.origin, ray.at(max_t));
+	let area = Aabb {
+	    min: Point{x: a.x.min(b.x), y: a.y.min(b.y)},
+	    max: Point{x: a.x.max(b.x), y: a.y.max(b.y)},
+	};
+	let mut candidates = vec![];
+	self.query_aabb(area, &mut candidates);
+	candidates.into_iter()
+	    .filter_map(|w| self.walls[w].raycast(ray).map(|t| (w, t)))
+	    .filter(|&(_, t)| t <= max_t)
+	    .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,135 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::body::*;
+use crate::dynamics::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// FixedTimestep
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+// Turns however much time a frame took into a whole number of `dt` steps, so
+// the physics behaves the same at any frame rate. What's left over carries on
+// to the next frame, and `alpha` says how far into the next step the frame
+// is, for drawing in between the last two states.
+pub struct FixedTimestep {
+    pub dt: f64,
+    // a slow frame runs at most this many steps and forgets the rest, rather
+    // than falling further behind every frame
+    pub max_steps: usize,
+    accumulator: f64,
+}
+impl FixedTimestep {
+
+    pub fn new(dt: f64) -> FixedTimestep {
+	FixedTimestep {
+	    dt,
+	    max_steps: 5,
+	    accumulator: 0.0,
+	}
+    }
+
+    // calls `step` once per whole step that fits in the time built up so far
+    // and returns how many times that was (never, if dt isn't above zero)
+    pub fn advance<F: FnMut(f64)>(&mut self, elapsed: f64, mut step: F) -> usize {
+	if !self.has_valid_dt() {
+	    return 0;
+	}
+	self.accumulator += elapsed;
+	let mut steps = 0;
+	while self.accumulator >= self.dt && steps < self.max_steps {
+	    step(self.dt);
+	    self.accumulator -= self.dt;
+	    steps += 1;
+	}
+	if steps == self.max_steps {
+	    self.accumulator = self.accumulator.min(self.dt);
+	}
+	steps
+    }
+
+    // 0 right after a step up to (almost) 1 just before the next
+    pub fn alpha(&self) -> f64 {
+	if !self.has_valid_dt() {
+	    return 0.0;
+	}
+	(self.accumulator / self.dt).clamp(0.0, 1.0)
+    }
+
+    // false for a dt of zero, below zero or NaN, which would step forever
+    // (or not at all) and make alpha NaN
+    fn has_valid_dt(&self) -> bool {
+	self.dt > 0.0 && self.dt.is_finite()
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// SimulationClock
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct BodyState {
+    pub position: Point,
+    pub angle: f64,
+}
+
+impl RigidBody {
+
+    pub fn state(&self) -> BodyState {
+	BodyState { position: self.position, angle: self.angle }
+    }
+
+    // the body's shape as it was (or would be) in `state`
+    pub fn shape_in(&self, state: BodyState) -> Shape {
+	RigidBody { position: state.position, angle: state.angle, ..*self }.shape()
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+// Steps a Simulation on a FixedTimestep and remembers where the bodies were
+// before the last step, so drawing can show them part way between.
+pub struct SimulationClock {
+    pub timestep: FixedTimestep,
+    previous: Vec<BodyState>,
+}
+impl SimulationClock {
+
+    pub fn new(dt: f64) -> SimulationClock {
+	SimulationClock {
+	    timestep: FixedTimestep::new(dt),
+	    previous: vec![],
+	}
+    }
+
+    // call from `update` with the frame's time
+    pub fn update(&mut self, sim: &mut Simulation, elapsed: f64) -> usize {
+	let previous = &mut self.previous;
+	self.timestep.advance(elapsed, |dt| {
+	    previous.clear();
+	    previous.extend(sim.bodies.iter().map(|b| b.state()));
+	    sim.step(dt);
+	})
+    }
+
+    // body i drawn `alpha` of the way from its last state to its current one
+    pub fn shape(&self, sim: &Simulation, i: usize) -> Shape {
+	let body = &sim.bodies[i];
+	let before = match self.previous.get(i) {
+	    Some(&state) => state,
+	    None => return body.shape(), // added since the last step
+	};
+	let t = self.timestep.alpha();
+	body.shape_in(BodyState {
+	    position: before.position.lerp(body.position, t),
+	    angle: before.angle + (body.angle - before.angle)*t,
+	})
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,105 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::static_geometry::StaticGeometry;
+
+// how close counts as touching when advancing a sweep
+const SWEEP_TOLERANCE: f64 = 1e-3;
+// running alongside a wall only closes the gap slowly, so allow plenty
+const MAX_SWEEP_ITERATIONS: usize = 64;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Sweeps
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// The mover first touches the obstacle after moving `t` (0 to 1) of the way.
+// `normal` points out of the obstacle towards the mover and `point` is where
+// they touch.
+pub struct SweepHit {
+    pub t: f64,
+    pub normal: Vector,
+    pub point: Point,
+}
+
+// the core (center point or segment) and radius of a shape
+fn core(s: Shape) -> (Segment, f64) {
+    match s {
+	Shape::Circle(c) => (Segment { p1: c.p, p2: c.p }, c.r),
+	Shape::Stad(s)   => (s.segment(), s.r),
+    }
+}
+
+impl Shape {
+
+    // gap between the surfaces (negative if they overlap) and the nearest
+    // points of the two cores
+    pub fn separation(&self, other: Shape) -> (f64, Point, Point) {
+	let (a, ra) = core(*self);
+	let (b, rb) = core(other);
+	let (pa, pb) = a.closest_points(b);
+	(pa.distance(pb) - ra - rb, pa, pb)
+    }
+
+    // Conservative advancement: the gap can't close faster than the shape
+    // moves, so it's always safe to move forward by the current gap. Repeat
+    // until the gap is closed or the motion used up. Only translation is
+    // swept, which is all a character needs.
+    pub fn sweep(&self, motion: Vector, other: Shape) -> Option<SweepHit> {
+	let speed = motion.magnitude();
+	let mut t = 0.0;
+	for _ in 0..MAX_SWEEP_ITERATIONS {
+	    let (gap, pa, pb) = self.translated(motion.scaled(t)).separation(other);
+	    if gap <= SWEEP_TOLERANCE {
+		return Some(sweep_hit(t, pa, pb, motion, other));
+	    }
+	    if speed == 0.0 {
+		return None;
+	    }
+	    t += gap / speed;
+	    if t > 1.0 {
+		return None;
+	    }
+	}
+	// Still closing in when the iterations ran out, which happens creeping
+	// up on a wall at a shallow angle. Everything up to t is known to be
+	// clear but past it may not be, so report a hit there rather than a
+	// miss the caller would move straight through.
+	let (_, pa, pb) = self.translated(motion.scaled(t)).separation(other);
+	Some(sweep_hit(t, pa, pb, motion, other))
+    }
+}
+
+// the hit at t, given the nearest points of the two cores there
+fn sweep_hit(t: f64, pa: Point, pb: Point, motion: Vector, other: Shape) -> SweepHit {
+    let away = pb.towards(pa);
+    let normal = if away.magnitude() > 0.0 {
+	away.normalized()
+    } else if motion.magnitude() > 0.0 {
+	motion.normalized().scaled(-1.0) // cores touching, push straight back
+    } else {
+	Vector{x: 1.0, y: 0.0} // not moving either, any direction will do
+    };
+    let (_, rb) = core(other);
+    SweepHit { t, normal, point: pb.plus(normal.scaled(rb)) }
+}
+
+impl StaticGeometry {
+
+    // the first wall the collider would run into moving by `motion`, as (wall, hit)
+    pub fn sweep(&self, collider: &Collider, motion: Vector) -> Option<(usize, SweepHit)> {
+	if !collider.filter.allows(self.filter) {
+	    return None;
+	}
+	let area = collider.shape.aabb().union(collider.shape.translated(motion).aabb());
+	let mut candidates = vec![];
+	self.query_aabb(area, &mut candidates);
+	candidates.sort_unstable();
+	candidates.into_iter()
+	    .filter_map(|w| collider.shape.sweep(motion, Shape::Stad(self.get(w))).map(|h| (w, h)))
+	    .min_by(|a, b| a.1.t.total_cmp(&b.1.t))
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,130 @@
+use crate::geometry::*;
+use crate::collider::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Tolerance
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Gaps this close to zero (either way) are rounding, not real space or real
+// overlap. In the same units as the shapes.
+pub const DEFAULT_EPSILON: f64 = 1e-6;
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+pub enum Proximity {
+    Separated,
+    // within epsilon of just touching
+    Touching,
+    Overlapping,
+}
+impl Proximity {
+
+    // touching or overlapping; what `collides` means
+    pub fn is_touching(&self) -> bool {
+	*self != Proximity::Separated
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+// Sorts a gap between two surfaces into separated, touching or overlapping.
+// Shapes sitting exactly against each other land in the middle of the
+// touching band, so rounding can't flip them back and forth from one frame
+// to the next the way a bare `<` does.
+pub struct Tolerance {
+    pub epsilon: f64,
+}
+impl Tolerance {
+
+    pub fn new(epsilon: f64) -> Tolerance {
+	Tolerance { epsilon }
+    }
+
+    pub fn classify(&self, gap: f64) -> Proximity {
+	if gap > self.epsilon {
+	    Proximity::Separated
+	} else if gap < -self.epsilon {
+	    Proximity::Overlapping
+	} else {
+	    Proximity::Touching
+	}
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Tolerance {
+	Tolerance::new(DEFAULT_EPSILON)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Gaps
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// The gap is the distance between the two surfaces, negative when they
+// overlap. `collides`, `contact`, `manifold` and `contains_point` all put a
+// gap like these through a Tolerance (the default one, or the one given to
+// their `_with` versions), so they agree on shapes that only just touch.
+// The batch tests in simd.rs compare squared distances instead (see there)
+// and the fixed point tests in fixed.rs are exact.
+impl Circle {
+
+    pub fn gap_circle(&self, other: Circle) -> f64 {
+	self.p.distance(other.p) - self.r - other.r
+    }
+
+    pub fn gap_stad(&self, other: Stad) -> f64 {
+	let q = other.segment().closest_point(self.p);
+	q.distance(self.p) - self.r - other.r
+    }
+}
+
+impl Stad {
+
+    pub fn gap_circle(&self, other: Circle) -> f64 {
+	other.gap_stad(*self)
+    }
+
+    pub fn gap_stad(&self, other: Stad) -> f64 {
+	let (pa, pb) = self.segment().closest_points(other.segment());
+	pa.distance(pb) - self.r - other.r
+    }
+}
+
+impl Shape {
+
+    pub fn gap(&self, other: Shape) -> f64 {
+	match (*self, other) {
+	    (Shape::Circle(a), Shape::Circle(b)) => a.gap_circle(b),
+	    (Shape::Circle(a), Shape::Stad(b))   => a.gap_stad(b),
+	    (Shape::Stad(a),   Shape::Circle(b)) => a.gap_circle(b),
+	    (Shape::Stad(a),   Shape::Stad(b))   => a.gap_stad(b),
+	}
+    }
+
+    pub fn proximity(&self, other: Shape, tolerance: Tolerance) -> Proximity {
+	tolerance.classify(self.gap(other))
+    }
+
+    // whether the surfaces are no more than `margin` apart ("is the player
+    // within 0.5 of the ledge"); overlapping counts
+    pub fn within(&self, other: Shape, margin: f64) -> bool {
+	self.gap(other) <= margin
+    }
+}
+
+impl Collider {
+
+    // as Shape::within, but never for pairs the filters keep apart
+    pub fn within(&self, other: &Collider, margin: f64) -> bool {
+	self.can_collide(other) && self.shape.within(other.shape, margin)
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,202 @@
+use crate::geometry::*;
+use crate::collider::*;
+use crate::broadphase;
+use crate::query::*;
+use crate::sensor::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Handle
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+#[derive(PartialOrd)]
+#[derive(Ord)]
+// A slot index plus the generation of the slot when the handle was made. When a
+// collider is removed its slot's generation is bumped, so old handles to it
+// stop resolving instead of quietly pointing at whatever reuses the slot.
+pub struct Handle {
+    index: u32,
+    generation: u32,
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// World
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+#[derive(Debug)]
+#[derive(Clone)]
+struct Slot {
+    generation: u32,
+    collider: Option<Collider>,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Copy)]
+pub struct RayHit {
+    pub handle: Handle,
+    pub t: f64,
+    pub point: Point,
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[derive(Default)]
+pub struct World {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    sensors: SensorTracker<Handle>,
+}
+impl World {
+
+    pub fn new() -> World {
+	World::default()
+    }
+
+    pub fn add(&mut self, collider: Collider) -> Handle {
+	match self.free.pop() {
+	    Some(index) => {
+		let slot = &mut self.slots[index as usize];
+		slot.collider = Some(collider);
+		Handle { index, generation: slot.generation }
+	    },
+	    None => {
+		self.slots.push(Slot { generation: 0, collider: Some(collider) });
+		Handle { index: self.slots.len() as u32 - 1, generation: 0 }
+	    },
+	}
+    }
+
+    pub fn add_circle(&mut self, c: Circle) -> Handle {
+	self.add(Collider::circle(c))
+    }
+
+    pub fn add_stad(&mut self, s: Stad) -> Handle {
+	self.add(Collider::stad(s))
+    }
+
+    pub fn remove(&mut self, h: Handle) -> Option<Collider> {
+	self.get(h)?;
+	let slot = &mut self.slots[h.index as usize];
+	slot.generation = slot.generation.wrapping_add(1);
+	self.free.push(h.index);
+	slot.collider.take()
+    }
+
+    pub fn contains(&self, h: Handle) -> bool {
+	self.get(h).is_some()
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&Collider> {
+	let slot = self.slots.get(h.index as usize)?;
+	if slot.generation != h.generation {
+	    return None;
+	}
+	slot.collider.as_ref()
+    }
+
+    pub fn get_mut(&mut self, h: Handle) -> Option<&mut Collider> {
+	let slot = self.slots.get_mut(h.index as usize)?;
+	if slot.generation != h.generation {
+	    return None;
+	}
+	slot.collider.as_mut()
+    }
+
+    // Replaces the shape, keeping the filter and user data. Returns false if
+    // the handle is stale.
+    pub fn set_shape(&mut self, h: Handle, shape: Shape) -> bool {
+	match self.get_mut(h) {
+	    Some(c) => {
+		c.shape = shape;
+		true
+	    },
+	    None => false,
+	}
+    }
+
+    pub fn translate(&mut self, h: Handle, v: Vector) -> bool {
+	match self.get_mut(h) {
+	    Some(c) => {
+		c.shape = c.shape.translated(v);
+		true
+	    },
+	    None => false,
+	}
+    }
+
+    pub fn len(&self) -> usize {
+	self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.len() == 0
+    }
+
+    // live colliders in slot order
+    pub fn iter(&self) -> impl Iterator<Item = (Handle, &Collider)> {
+	self.slots.iter().enumerate().filter_map(|(n, slot)| {
+	    slot.collider.as_ref().map(|c| {
+		(Handle { index: n as u32, generation: slot.generation }, c)
+	    })
+	})
+    }
+
+    // Every touching pair allowed by the colliders' filters, as (lower handle,
+    // higher handle) in sorted order. Pairs with a sensor in them are
+    // included; check `Collider::sensor` to skip those.
+    pub fn colliding_pairs(&self) -> Vec<(Handle, Handle)> {
+	let (handles, colliders): (Vec<Handle>, Vec<Collider>) =
+	    self.iter().map(|(h, c)| (h, *c)).unzip();
+	broadphase::colliding_pairs(&colliders).into_iter()
+	    .map(|(i, j)| (handles[i], handles[j]))
+	    .collect()
+    }
+
+    // Diffs the sensors' overlaps against the last call's. Call it once per
+    // step, after moving things; removed colliders get their Exit here too.
+    pub fn update_sensors(&mut self) -> Vec<SensorEvent<Handle>> {
+	let (handles, colliders): (Vec<Handle>, Vec<Collider>) =
+	    self.iter().map(|(h, c)| (h, *c)).unzip();
+	let overlaps = sensor_overlaps(&colliders).into_iter()
+	    .map(|(i, j)| (handles[i], handles[j]));
+	self.sensors.update(overlaps)
+    }
+
+    // every collider (other than `probe` itself, if it's in the world) whose
+    // surface is no more than `margin` from the probe's and whose filter
+    // lets the two touch
+    pub fn within(&self, probe: &Collider, margin: f64) -> Vec<Handle> {
+	self.iter()
+	    .filter(|(_, c)| probe.within(c, margin))
+	    .map(|(h, _)| h)
+	    .collect()
+    }
+
+    // every collider containing p that `filter` allows (both ways, as in
+    // CollisionFilter::allows)
+    pub fn query_point(&self, p: Point, filter: CollisionFilter) -> Vec<Handle> {
+	self.iter()
+	    .filter(|(_, c)| filter.allows(c.filter) && c.shape.contains_point(p))
+	    .map(|(h, _)| h)
+	    .collect()
+    }
+
+    // the first collider along the ray (within max_t) that `filter` allows
+    pub fn raycast(&self, ray: Ray, max_t: f64, filter: CollisionFilter) -> Option<RayHit> {
+	self.iter()
+	    .filter(|(_, c)| filter.allows(c.filter))
+	    .filter_map(|(h, c)| c.shape.raycast(ray).map(|t| (h, t)))
+	    .filter(|&(_, t)| t <= max_t)
+	    .min_by(|a, b| a.1.total_cmp(&b.1))
+	    .map(|(handle, t)| RayHit { handle, t, point: ray.at(t) })
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
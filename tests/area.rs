@@ -0,0 +1,141 @@
+use std::f64::consts::PI;
+
+use stad_demos::geometry::*;
+
+mod common;
+use common::Lcg;
+
+// The circle/stadium overlap is checked against closed forms where there is
+// one, and against counting grid points where there isn't.
+
+fn close(a: f64, b: f64, rel: f64) -> bool {
+    (a - b).abs() <= rel * a.abs().max(b.abs()).max(1.0)
+}
+
+fn stad() -> Stad {
+    Stad { p1: Point { x: 10.0, y: 20.0 }, p2: Point { x: 70.0, y: 50.0 }, r: 12.0 }
+}
+
+// the area of the overlap by counting the centers of an n by n grid of cells
+// over the circle's bounding box
+fn sampled_overlap(c: Circle, s: Stad, n: usize) -> f64 {
+    let cell = 2.0 * c.r / n as f64;
+    let mut inside = 0;
+    for i in 0..n {
+	for j in 0..n {
+	    let p = Point {
+		x: c.p.x - c.r + (i as f64 + 0.5) * cell,
+		y: c.p.y - c.r + (j as f64 + 0.5) * cell,
+	    };
+	    if p.distance(c.p) < c.r && s.segment().closest_point(p).distance(p) < s.r {
+		inside += 1;
+	    }
+	}
+    }
+    inside as f64 * cell * cell
+}
+
+#[test]
+fn circle_inside_the_stadium() {
+    let s = stad();
+    let c = Circle { p: s.p1.lerp(s.p2, 0.4), r: 7.0 };
+    assert!(close(c.intersection_area_stad(s), PI * 49.0, 1e-12));
+    assert!(close(s.intersection_area_circle(c), PI * 49.0, 1e-12));
+}
+
+#[test]
+fn stadium_inside_the_circle() {
+    let s = stad();
+    let c = Circle { p: s.centroid().plus(Vector { x: 3.0, y: -2.0 }), r: 60.0 };
+    assert!(close(c.intersection_area_stad(s), s.area(), 1e-12));
+    assert!(close(s.intersection_area_circle(c), s.area(), 1e-12));
+}
+
+#[test]
+fn circle_equal_to_an_end_cap() {
+    let s = stad();
+    for &p in [s.p1, s.p2].iter() {
+	let c = Circle { p, r: s.r };
+	assert!(close(c.intersection_area_stad(s), c.area(), 1e-12));
+    }
+}
+
+#[test]
+fn zero_length_stadium_is_a_circle() {
+    let mut rng = Lcg(50);
+    for _ in 0..200 {
+	let p = Point { x: rng.next() * 40.0, y: rng.next() * 40.0 };
+	let r = 1.0 + rng.next() * 15.0;
+	let c = Circle { p: Point { x: rng.next() * 40.0, y: rng.next() * 40.0 }, r: 1.0 + rng.next() * 15.0 };
+	let s = Stad { p1: p, p2: p, r };
+	assert_eq!(c.intersection_area_stad(s), c.intersection_area_circle(Circle { p, r }));
+    }
+}
+
+#[test]
+fn partial_overlaps_match_sampling() {
+    let s = stad();
+    let mut rng = Lcg(7);
+    let mut partial = 0;
+    while partial < 40 {
+	let c = Circle {
+	    p: Point { x: -10.0 + rng.next() * 100.0, y: rng.next() * 70.0 },
+	    r: 3.0 + rng.next() * 25.0,
+	};
+	let exact = c.intersection_area_stad(s);
+	if exact == 0.0 || close(exact, c.area(), 1e-9) || close(exact, s.area(), 1e-9) {
+	    continue;
+	}
+	partial += 1;
+	// Counting cells only goes wrong in the cells along the edge, and those
+	// mostly cancel out. A quarter of their area still leaves plenty of
+	// room, and an overlap that's wrong by a sliver is well outside it.
+	let sampled = sampled_overlap(c, s, 800);
+	let cell = 2.0 * c.r / 800.0;
+	let slack = (2.0 * PI * c.r + s.perimeter()) * cell / 4.0;
+	assert!((exact - sampled).abs() <= slack, "{:?} {} vs {} sampled", c, exact, sampled);
+    }
+}
+
+// what mass_properties worked out for these at density 1 before it used
+// `area`
+#[test]
+fn stadium_second_moment_matches_the_old_mass_properties() {
+    let cases = [
+	(Stad { p1: Point { x: 0.0, y: 0.0 }, p2: Point { x: 40.0, y: 0.0 }, r: 10.0 }, 1114.1592653589794, 328038.33607820736),
+	(Stad { p1: Point { x: 100.0, y: 50.0 }, p2: Point { x: 100.0, y: 80.0 }, r: 5.0 }, 378.53981633974485, 48653.2063806894),
+	(Stad { p1: Point { x: -3.0, y: 4.0 }, p2: Point { x: 9.0, y: -12.0 }, r: 2.5 }, 119.63495408493621, 5983.187973342379),
+    ];
+    for &(s, area, moment) in cases.iter() {
+	assert!(close(s.area(), area, 1e-12));
+	assert!(close(s.second_moment(), moment, 1e-12));
+    }
+}
+
+#[test]
+fn polygon_centroid_and_second_moment() {
+    // a 4 by 2 rectangle: centroid in the middle, and (w^2 + h^2) wh/12
+    let rect = Polygon::try_new(vec![
+	Point { x: 100.0, y: 10.0 },
+	Point { x: 104.0, y: 10.0 },
+	Point { x: 104.0, y: 12.0 },
+	Point { x: 100.0, y: 12.0 },
+    ]).unwrap();
+    let c = rect.centroid().unwrap();
+    assert!(close(c.x, 102.0, 1e-12) && close(c.y, 11.0, 1e-12));
+    assert!(close(rect.second_moment().unwrap(), 20.0 * 8.0 / 12.0, 1e-12));
+    // the same the other way round
+    let mut points = rect.points.clone();
+    points.reverse();
+    assert!(close(Polygon::new(points).second_moment().unwrap(), 20.0 * 8.0 / 12.0, 1e-12));
+}
+
+#[test]
+fn polygon_without_area_has_no_centroid() {
+    let empty = Polygon::new(vec![]);
+    assert_eq!(empty.centroid(), Err(GeometryError::DegeneratePolygon));
+    assert_eq!(empty.second_moment(), Err(GeometryError::DegeneratePolygon));
+    let flat = Polygon::new(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }, Point { x: 2.0, y: 2.0 }]);
+    assert_eq!(flat.centroid(), Err(GeometryError::DegeneratePolygon));
+    assert_eq!(flat.second_moment(), Err(GeometryError::DegeneratePolygon));
+}
@@ -0,0 +1,53 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::broadphase::*;
+use stad_demos::batch::*;
+
+mod common;
+use common::Lcg;
+
+// The batch functions must give back exactly what calling the scalar query on
+// each pair in turn does, in the same order, however the work is split up.
+// Run with and without `--features parallel`; with it, the same checks are
+// repeated on rayon pools of several sizes.
+
+fn check_all() {
+    let mut rng = Lcg(27);
+    let stads: Vec<(Stad, Stad)> = (0..500).map(|_| (rng.stad(0.0, 400.0, 30.0), rng.stad(0.0, 400.0, 30.0))).collect();
+    let shapes: Vec<(Shape, Shape)> = (0..500).map(|_| (rng.shape(0.0, 400.0, 30.0), rng.shape(0.0, 400.0, 30.0))).collect();
+
+    let hits = collides_stad_batch(&stads);
+    let expected: Vec<bool> = stads.iter().map(|(a, b)| a.collides_stad(*b)).collect();
+    // a mix, so a shuffled order couldn't pass by accident
+    assert!(expected.iter().any(|&h| h) && expected.iter().any(|&h| !h));
+    assert_eq!(hits, expected);
+
+    let expected: Vec<_> = stads.iter().map(|(a, b)| a.contact_stad(*b)).collect();
+    assert_eq!(contact_stad_batch(&stads), expected);
+
+    let expected: Vec<_> = shapes.iter().map(|(a, b)| a.contact(*b)).collect();
+    assert_eq!(contact_batch(&shapes), expected);
+
+    // some on a layer the rest don't touch, so the filters matter too
+    let colliders: Vec<Collider> = (0..300).map(|n| {
+	let filter = if rng.next() < 0.2 { CollisionFilter::new(2, 2) } else { CollisionFilter::default() };
+	Collider::new(rng.shape(0.0, 400.0, 30.0), filter, n)
+    }).collect();
+    let pairs = candidate_pairs(&colliders);
+    let expected: Vec<_> = pairs.iter().map(|&(i, j)| colliders[i].contact(&colliders[j])).collect();
+    assert_eq!(collider_contact_batch(&colliders, &pairs), expected);
+}
+
+#[test]
+fn same_results_in_input_order() {
+    check_all();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn same_results_for_every_pool_size() {
+    for &threads in [1, 2, 3, 8].iter() {
+	let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+	pool.install(check_all);
+    }
+}
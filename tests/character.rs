@@ -0,0 +1,161 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::static_geometry::*;
+use stad_demos::character::*;
+
+// y is down, as on screen. The floor's top is at y = 740.
+const FLOOR: f64 = 740.0;
+
+fn level(extra: &[Stad]) -> StaticGeometry {
+    let mut walls = vec![Stad::new(20.0, 760.0, 1180.0, 760.0, 20.0)];
+    walls.extend_from_slice(extra);
+    StaticGeometry::new(&walls, CollisionFilter::default())
+}
+
+// an upright capsule 80 tall with its feet at `feet`
+fn player(x: f64, feet: f64) -> CharacterController {
+    CharacterController::new(Stad::new(x, feet - 60.0, x, feet - 20.0, 20.0), CharacterSettings::default())
+}
+
+fn feet(c: &CharacterController) -> f64 {
+    c.stad().p2.y + c.stad().r
+}
+
+fn close(a: f64, b: f64, within: f64) -> bool {
+    (a - b).abs() <= within
+}
+
+#[test]
+fn sweep_stops_where_the_shapes_first_touch() {
+    let circle = Shape::Circle(Circle { p: Point { x: 0.0, y: 0.0 }, r: 10.0 });
+    let wall = Shape::Stad(Stad::new(60.0, -50.0, 60.0, 50.0, 5.0));
+    let hit = circle.sweep(Vector { x: 100.0, y: 0.0 }, wall).unwrap();
+    // touching once the circle's center reaches x = 45
+    assert!(close(hit.t, 0.45, 1e-4), "{:?}", hit);
+    assert!(close(hit.normal.x, -1.0, 1e-9) && close(hit.normal.y, 0.0, 1e-9), "{:?}", hit);
+    assert!(close(hit.point.x, 55.0, 1e-9) && close(hit.point.y, 0.0, 1e-9), "{:?}", hit);
+
+    assert!(circle.sweep(Vector { x: 40.0, y: 0.0 }, wall).is_none());
+    assert!(circle.sweep(Vector { x: 0.0, y: 100.0 }, wall).is_none());
+}
+
+#[test]
+fn sweep_that_runs_out_of_iterations_stops_short() {
+    // Closing on the wall at about 1/20 of the speed, so each step of the
+    // advancement only takes off a twentieth of the gap and it can't get
+    // within the tolerance before giving up. The true first touch is at
+    // t = 0.2.
+    let circle = Shape::Circle(Circle { p: Point { x: 0.0, y: 0.0 }, r: 10.0 });
+    let wall = Shape::Stad(Stad::new(-100.0, 21.0, 3000.0, 21.0, 1.0));
+    let motion = Vector { x: 1000.0, y: 50.0 };
+    let hit = circle.sweep(motion, wall).unwrap();
+    assert!(hit.t <= 0.2 && hit.t > 0.15, "{:?}", hit);
+    assert!(!circle.translated(motion.scaled(hit.t)).collides(wall));
+    assert!(close(hit.normal.x, 0.0, 1e-9) && close(hit.normal.y, -1.0, 1e-9), "{:?}", hit);
+}
+
+#[test]
+fn sliding_under_a_shallow_ceiling_does_not_go_through_it() {
+    // in the air under a ceiling that comes down 1 for every 20 across, with
+    // nothing else around; its underside is 18 above the head to start with
+    let ceiling = Stad::new(0.0, 295.0, 2000.0, 395.0, 2.0);
+    let walls = StaticGeometry::new(&[ceiling], CollisionFilter::default());
+    let mut c = player(100.0, 400.0);
+    c.move_by(&walls, Vector { x: 1000.0, y: 0.0 });
+    let s = c.stad();
+    assert!(!Shape::Stad(s).collides(Shape::Stad(ceiling)), "{:?}", s);
+    let underside = 295.0 + s.p1.x * 0.05 + 2.0;
+    assert!(s.p1.y - s.r > underside, "head at {} under {}", s.p1.y - s.r, underside);
+}
+
+#[test]
+fn ground_probe_finds_the_floor_under_the_feet() {
+    let walls = level(&[]);
+    let mut c = player(100.0, 660.0);
+    c.move_by(&walls, Vector { x: 0.0, y: 200.0 });
+    assert!(c.on_ground);
+    let n = c.ground_normal.unwrap();
+    assert!(close(n.x, 0.0, 1e-6) && close(n.y, -1.0, 1e-6), "{:?}", n);
+    // stopped a skin's width above the floor
+    assert!(close(feet(&c), FLOOR - c.settings.skin, 0.01), "{}", feet(&c));
+
+    // further up than the probe reaches
+    c.move_by(&walls, Vector { x: 0.0, y: -10.0 });
+    assert!(!c.on_ground);
+    assert!(c.ground_normal.is_none());
+}
+
+#[test]
+fn only_gentle_slopes_are_walkable() {
+    let c = player(100.0, FLOOR);
+    let at = |deg: f64| {
+	let a = deg.to_radians();
+	Vector { x: a.sin(), y: -a.cos() }
+    };
+    assert!(c.is_walkable(at(0.0)));
+    assert!(c.is_walkable(at(45.0)));
+    assert!(c.is_walkable(at(-45.0)));
+    assert!(!c.is_walkable(at(55.0)));
+    assert!(!c.is_walkable(at(90.0)));
+    assert!(!c.is_walkable(at(180.0)));
+}
+
+// walks right a few pixels a step, pressed down a little as gravity would
+fn walk_right(c: &mut CharacterController, walls: &StaticGeometry, steps: usize) {
+    for _ in 0..steps {
+	c.move_by(walls, Vector { x: 4.0, y: 2.0 });
+    }
+}
+
+#[test]
+fn walks_up_a_low_ledge() {
+    // 10 high, under the 16 step height
+    let walls = level(&[Stad::new(300.0, 735.0, 700.0, 735.0, 5.0)]);
+    let mut c = player(100.0, FLOOR - 0.5);
+    walk_right(&mut c, &walls, 100);
+    assert!(c.stad().p1.x > 400.0, "stuck at {}", c.stad().p1.x);
+    assert!(c.on_ground);
+    // standing on top of it, not sunk into it
+    assert!(feet(&c) <= 730.0 && feet(&c) > 729.0, "{}", feet(&c));
+}
+
+#[test]
+fn blocked_by_a_high_ledge() {
+    // 25 high, over the 16 step height
+    let walls = level(&[Stad::new(300.0, 720.0, 700.0, 720.0, 5.0)]);
+    let mut c = player(100.0, FLOOR - 0.5);
+    walk_right(&mut c, &walls, 100);
+    // pressed up against the ledge's rounded end
+    assert!(c.stad().p1.x < 300.0 - 20.0, "went through to {}", c.stad().p1.x);
+    assert!(c.stad().p1.x > 250.0, "stopped early at {}", c.stad().p1.x);
+    assert!(c.on_ground);
+    assert!(close(feet(&c), FLOOR - c.settings.skin, 0.01), "{}", feet(&c));
+}
+
+#[test]
+fn slides_along_a_wall() {
+    // a wall whose left face is at x = 380
+    let walls = level(&[Stad::new(400.0, 100.0, 400.0, 740.0, 20.0)]);
+    let mut c = player(300.0, 400.0);
+    // diagonally into the wall: the part into it is dropped, the rest kept
+    let moved = c.move_by(&walls, Vector { x: 200.0, y: 50.0 });
+    assert!(close(moved.y, 50.0, 1e-6), "{:?}", moved);
+    assert!(close(c.stad().p1.x + c.stad().r, 380.0 - c.settings.skin, 0.01), "{:?}", c.stad());
+    assert!(!c.on_ground);
+    // and never into the wall, however it's pushed
+    for _ in 0..50 {
+	c.move_by(&walls, Vector { x: 7.0, y: -3.0 });
+	assert!(c.stad().p1.x + c.stad().r <= 380.0, "{:?}", c.stad());
+    }
+}
+
+#[test]
+fn a_filter_that_skips_the_walls_walks_through_them() {
+    let walls = level(&[Stad::new(300.0, 600.0, 300.0, 740.0, 10.0)]);
+    let mut c = player(100.0, FLOOR - 10.0);
+    c.set_filter(CollisionFilter::new(2, 2));
+    assert_eq!(c.collider().filter, CollisionFilter::new(2, 2));
+    c.move_by(&walls, Vector { x: 400.0, y: 0.0 });
+    assert!(close(c.stad().p1.x, 500.0, 1e-9));
+    assert!(!c.on_ground);
+}
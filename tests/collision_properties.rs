@@ -0,0 +1,195 @@
+use proptest::prelude::*;
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::simd::*;
+use stad_demos::interval::*;
+use stad_demos::tolerance::*;
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Generators
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+// Coordinates are either anywhere or on a coarse grid, so plenty of shapes
+// end up sharing an x or a y (or exactly touching).
+fn coord() -> impl Strategy<Value = f64> {
+    prop_oneof![
+	-100.0..100.0f64,
+	(-20i32..20).prop_map(|i| i as f64 * 5.0),
+    ]
+}
+
+fn point() -> impl Strategy<Value = Point> {
+    (coord(), coord()).prop_map(|(x, y)| Point { x, y })
+}
+
+// general, horizontal, vertical and zero length
+fn segment() -> impl Strategy<Value = Segment> {
+    prop_oneof![
+	(point(), point()).prop_map(|(p1, p2)| Segment { p1, p2 }),
+	(point(), coord()).prop_map(|(p1, d)| Segment { p1, p2: Point { x: p1.x + d, y: p1.y } }),
+	(point(), coord()).prop_map(|(p1, d)| Segment { p1, p2: Point { x: p1.x, y: p1.y + d } }),
+	point().prop_map(|p| Segment { p1: p, p2: p }),
+    ]
+}
+
+fn radius() -> impl Strategy<Value = f64> {
+    prop_oneof![
+	Just(0.0),
+	0.0..30.0f64,
+	(0i32..6).prop_map(|i| i as f64 * 5.0),
+    ]
+}
+
+fn circle() -> impl Strategy<Value = Circle> {
+    (point(), radius()).prop_map(|(p, r)| Circle { p, r })
+}
+
+fn stad() -> impl Strategy<Value = Stad> {
+    (segment(), radius()).prop_map(|(s, r)| Stad { p1: s.p1, p2: s.p2, r })
+}
+
+fn shape() -> impl Strategy<Value = Shape> {
+    prop_oneof![
+	circle().prop_map(Shape::Circle),
+	stad().prop_map(Shape::Stad),
+    ]
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Reference
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+const SAMPLES: usize = 200;
+
+// every shape is a core segment (a point for circles) grown by a radius
+fn core(s: Shape) -> (Segment, f64) {
+    match s {
+	Shape::Circle(c) => (Segment { p1: c.p, p2: c.p }, c.r),
+	Shape::Stad(s)   => (s.segment(), s.r),
+    }
+}
+
+fn samples(s: Segment) -> Vec<Point> {
+    (0..SAMPLES).map(|i| s.p1.lerp(s.p2, i as f64 / (SAMPLES - 1) as f64)).collect()
+}
+
+// The gap from the nearest pair of sample points, and how far over the true
+// gap that can be: the true nearest points are each within half a step of a
+// sample.
+fn reference_gap(a: Shape, b: Shape) -> (f64, f64) {
+    let ((sa, ra), (sb, rb)) = (core(a), core(b));
+    let (pa, pb) = (samples(sa), samples(sb));
+    let mut nearest = f64::INFINITY;
+    for p in pa.iter() {
+	for q in pb.iter() {
+	    nearest = nearest.min(p.distance(*q));
+	}
+    }
+    let step = (sa.p1.distance(sa.p2) + sb.p1.distance(sb.p2)) / (SAMPLES - 1) as f64;
+    (nearest - ra - rb, step / 2.0)
+}
+
+// the rounding any of the gaps can pick up at these coordinates
+const SLOP: f64 = 1e-9;
+
+// whether `collides` could reasonably go either way for this gap
+fn marginal(gap: f64, error: f64) -> bool {
+    (gap - DEFAULT_EPSILON).abs() <= error + SLOP
+}
+
+fn rotated(s: Shape, angle: f64) -> Shape {
+    let origin = Point { x: 0.0, y: 0.0 };
+    let turn = |p: Point| origin.plus(origin.towards(p).rotated(angle));
+    match s {
+	Shape::Circle(c) => Shape::Circle(Circle { p: turn(c.p), r: c.r }),
+	Shape::Stad(s)   => Shape::Stad(Stad { p1: turn(s.p1), p2: turn(s.p2), r: s.r }),
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+
+
+/////////////////////////////////////////////////////////////////////////////////////
+// Properties
+//vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv
+proptest! {
+
+    #[test]
+    fn collides_is_symmetric(a in shape(), b in shape()) {
+	prop_assert!((a.gap(b) - b.gap(a)).abs() <= SLOP);
+	if !marginal(a.gap(b), 0.0) {
+	    prop_assert_eq!(a.collides(b), b.collides(a));
+	}
+    }
+
+    #[test]
+    fn translation_invariant(a in shape(), b in shape(), x in coord(), y in coord()) {
+	let v = Vector { x, y };
+	let (at, bt) = (a.translated(v), b.translated(v));
+	prop_assert!((a.gap(b) - at.gap(bt)).abs() <= SLOP * 10.0);
+	if !marginal(a.gap(b), SLOP * 10.0) {
+	    prop_assert_eq!(a.collides(b), at.collides(bt));
+	}
+    }
+
+    #[test]
+    fn rotation_invariant(a in shape(), b in shape(), angle in -3.2..3.2f64) {
+	let (ar, br) = (rotated(a, angle), rotated(b, angle));
+	prop_assert!((a.gap(b) - ar.gap(br)).abs() <= SLOP * 10.0);
+	if !marginal(a.gap(b), SLOP * 10.0) {
+	    prop_assert_eq!(a.collides(b), ar.collides(br));
+	}
+    }
+
+    #[test]
+    fn agrees_with_sampled_reference(a in shape(), b in shape()) {
+	let gap = a.gap(b);
+	let (reference, error) = reference_gap(a, b);
+	// sampling can only miss the nearest points, never get closer, except
+	// when the segments cross (which samples don't see)
+	let crossing = core(a).0.is_overlap(core(b).0);
+	if !crossing {
+	    prop_assert!(gap <= reference + SLOP);
+	    prop_assert!(reference <= gap + error + SLOP);
+	    if !marginal(gap, error) {
+		prop_assert_eq!(a.collides(b), reference <= DEFAULT_EPSILON);
+	    }
+	} else {
+	    prop_assert!(a.collides(b));
+	}
+    }
+
+    #[test]
+    fn contact_exactly_when_collides(a in shape(), b in shape()) {
+	// the same gap through the same tolerance, so even touching agrees
+	let contact = a.contact(b);
+	prop_assert_eq!(contact.is_some(), a.collides(b));
+	prop_assert_eq!(a.manifold(b).is_some(), a.collides(b));
+	if let Some(c) = contact {
+	    prop_assert!(c.depth >= 0.0);
+	}
+    }
+
+    #[test]
+    fn batch_agrees_with_scalar(s in stad(), others in prop::collection::vec(stad(), 1..20)) {
+	let batch = StadBatch::from_stads(&others);
+	for (o, hit) in others.iter().zip(batch.collides_stad(s)) {
+	    if !marginal(s.gap_stad(*o), SLOP) {
+		prop_assert_eq!(s.collides_stad(*o), hit);
+	    }
+	}
+    }
+
+    #[test]
+    fn certified_answers_are_right(a in shape(), b in shape()) {
+	match a.collides_certified(b) {
+	    Certainty::Yes => prop_assert!(a.collides(b)),
+	    Certainty::No  => prop_assert!(!a.collides(b)),
+	    Certainty::Uncertain => prop_assert!(marginal(a.gap(b), SLOP)),
+	}
+    }
+}
+//^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
@@ -0,0 +1,47 @@
+// Shared by the integration tests (and the broadphase bench) that want the
+// same pseudo-random shapes on every run and every machine without pulling
+// in a rand crate. Each file that uses it declares `mod common;` and only
+// uses some of it.
+#![allow(dead_code)]
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+
+pub struct Lcg(pub u64);
+impl Lcg {
+
+    // in [0, 1), from the top 53 bits
+    pub fn next(&mut self) -> f64 {
+	(self.step() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // 31 bits, from the top, since an LCG's low bits repeat quickly
+    pub fn bits(&mut self) -> u64 {
+	self.step() >> 33
+    }
+
+    pub fn range(&mut self, lo: f64, hi: f64) -> f64 {
+	lo + self.next() * (hi - lo)
+    }
+
+    // both ends anywhere in the square [lo, hi) by [lo, hi)
+    pub fn stad(&mut self, lo: f64, hi: f64, max_r: f64) -> Stad {
+	let (x1, y1, x2, y2) = (self.range(lo, hi), self.range(lo, hi), self.range(lo, hi), self.range(lo, hi));
+	Stad { p1: Point { x: x1, y: y1 }, p2: Point { x: x2, y: y2 }, r: self.next() * max_r }
+    }
+
+    // about a third circles, the rest stadiums
+    pub fn shape(&mut self, lo: f64, hi: f64, max_r: f64) -> Shape {
+	if self.next() < 0.3 {
+	    let s = self.stad(lo, hi, max_r);
+	    Shape::Circle(Circle { p: s.p1, r: s.r })
+	} else {
+	    Shape::Stad(self.stad(lo, hi, max_r))
+	}
+    }
+
+    fn step(&mut self) -> u64 {
+	self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+	self.0
+    }
+}
@@ -0,0 +1,98 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::dynamics::*;
+use stad_demos::solver::*;
+
+const DT: f64 = 1.0 / 60.0;
+
+// a long fixed floor with its top at y = 730 (y is down, as in the demos)
+fn floor(sim: &mut Simulation) {
+    sim.add_body(RigidBody::fixed(Collider::stad(Stad::new(100.0, 750.0, 1100.0, 750.0, 20.0))));
+}
+
+// n flat stadiums 40 tall dropped into a stack, each a pixel above the last
+fn stack(n: usize) -> Simulation {
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    // so that what's tested is the solver holding the stack up
+    sim.sleep.enabled = false;
+    floor(&mut sim);
+    for i in 0..n {
+	let y = 750.0 - 40.0 - 40.0*i as f64 - 1.0;
+	sim.add_body(RigidBody::new(Collider::stad(Stad::new(560.0, y, 640.0, y, 20.0)), 1.0));
+    }
+    sim
+}
+
+fn deepest_contact(sim: &Simulation) -> f64 {
+    sim.contacts.iter().map(|c| c.contact.depth).fold(0.0, f64::max)
+}
+
+#[test]
+fn stack_comes_to_rest_without_sinking() {
+    let mut sim = stack(6);
+    for _ in 0..300 {
+	sim.step(DT);
+    }
+    let settled: Vec<Point> = sim.bodies.iter().map(|b| b.position).collect();
+    for _ in 0..300 {
+	sim.step(DT);
+	// never more overlap than the solver's allowance
+	assert!(deepest_contact(&sim) <= sim.solver.settings.slop + 0.05, "{}", deepest_contact(&sim));
+    }
+    for (i, body) in sim.bodies.iter().enumerate().skip(1) {
+	// resting, level and where it was five seconds ago
+	assert!(body.velocity.magnitude() < 0.1, "body {} still moving at {:?}", i, body.velocity);
+	assert!(body.angle.abs() < 0.01, "body {} tipped to {}", i, body.angle);
+	assert!(body.position.distance(settled[i]) < 0.01, "body {} crept from {:?} to {:?}",
+		i, settled[i], body.position);
+	// each layer at most the slop into the one below
+	let resting_y = 710.0 - 40.0*(i - 1) as f64;
+	assert!(body.position.y >= resting_y - 0.1, "body {} is above its rest height", i);
+	assert!(body.position.y <= resting_y + i as f64 * (sim.solver.settings.slop + 0.05),
+		"body {} sank to {}", i, body.position.y);
+    }
+}
+
+#[test]
+fn a_body_falls_with_gravity_until_it_lands() {
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    floor(&mut sim);
+    let ball = sim.add_body(RigidBody::new(Collider::circle(Circle { p: Point { x: 600.0, y: 100.0 }, r: 20.0 }), 1.0));
+    sim.step(DT);
+    // semi-implicit Euler: the velocity is updated first and then moves it
+    assert!((sim.bodies[ball].velocity.y - 900.0*DT).abs() < 1e-9);
+    assert!((sim.bodies[ball].position.y - (100.0 + 900.0*DT*DT)).abs() < 1e-9);
+    for _ in 0..300 {
+	sim.step(DT);
+    }
+    assert!((sim.bodies[ball].position.y - 710.0).abs() <= sim.solver.settings.slop + 0.05);
+    assert!((sim.bodies[ball].position.x - 600.0).abs() < 1e-6);
+}
+
+// Starting each step from the last step's impulses lets a few iterations do
+// the work of many, so a tall stack on a tight iteration budget overlaps less
+// and jitters less with warm starting than without.
+#[test]
+fn warm_starting_leaves_less_penetration() {
+    let settle = |warm_starting: bool| {
+	let mut sim = stack(6);
+	sim.solver.settings.iterations = 4;
+	sim.solver.settings.warm_starting = warm_starting;
+	for _ in 0..300 {
+	    sim.step(DT);
+	}
+	let (mut depth, mut speed) = (0.0f64, 0.0f64);
+	for _ in 0..300 {
+	    sim.step(DT);
+	    depth = depth.max(deepest_contact(&sim));
+	    speed = sim.bodies.iter().map(|b| b.velocity.magnitude()).fold(speed, f64::max);
+	}
+	(depth, speed)
+    };
+    let (warm_depth, warm_speed) = settle(true);
+    let (cold_depth, cold_speed) = settle(false);
+    assert!(warm_depth <= SolverSettings::default().slop + 0.05, "{}", warm_depth);
+    assert!(warm_depth * 2.0 < cold_depth, "warm {} cold {}", warm_depth, cold_depth);
+    assert!(warm_speed * 10.0 < cold_speed, "warm {} cold {}", warm_speed, cold_speed);
+}
@@ -0,0 +1,80 @@
+use proptest::prelude::*;
+
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::broadphase::*;
+
+// Sweep and prune has to give exactly the pairs that checking every pair's
+// filter and box would, so nothing a filter rejects ever gets through and
+// nothing it allows gets lost.
+
+fn brute_force_candidates(colliders: &[Collider]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    for i in 0..colliders.len() {
+	for j in i+1..colliders.len() {
+	    let (a, b) = (&colliders[i], &colliders[j]);
+	    if a.filter.allows(b.filter) && a.shape.aabb().overlaps(b.shape.aabb()) {
+		pairs.push((i, j));
+	    }
+	}
+    }
+    pairs
+}
+
+fn brute_force_colliding(colliders: &[Collider]) -> Vec<(usize, usize)> {
+    brute_force_candidates(colliders).into_iter()
+	.filter(|&(i, j)| colliders[i].collides(&colliders[j]))
+	.collect()
+}
+
+// only three layers, so groups and masks often share one and often don't
+fn layers() -> impl Strategy<Value = u32> {
+    0u32..8
+}
+
+fn collider() -> impl Strategy<Value = Collider> {
+    let circle = (0.0..200.0f64, 0.0..200.0f64, 1.0..30.0f64)
+	.prop_map(|(x, y, r)| Shape::Circle(Circle { p: Point { x, y }, r }));
+    let stad = (0.0..200.0f64, 0.0..200.0f64, -60.0..60.0f64, -60.0..60.0f64, 1.0..20.0f64)
+	.prop_map(|(x, y, dx, dy, r)| Shape::Stad(Stad::new(x, y, x + dx, y + dy, r)));
+    (prop_oneof![circle, stad], layers(), layers())
+	.prop_map(|(shape, group, mask)| Collider::new(shape, CollisionFilter::new(group, mask), 0))
+}
+
+#[test]
+fn both_sides_have_to_allow_the_other() {
+    let a = CollisionFilter::new(0b001, 0b010);
+    let b = CollisionFilter::new(0b010, 0b100);
+    // a's mask takes b's group, but b's mask doesn't take a's
+    assert!(!a.allows(b));
+    assert!(!b.allows(a));
+    let c = CollisionFilter::new(0b100, 0b011);
+    assert!(b.allows(c) && c.allows(b));
+    // no group at all touches nothing, even with everything in the mask
+    let nothing = CollisionFilter::new(0, CollisionFilter::ALL);
+    assert!(!nothing.allows(CollisionFilter::default()));
+    assert!(!CollisionFilter::default().allows(nothing));
+}
+
+#[test]
+fn one_sided_pairs_are_left_out() {
+    let shape = Shape::Circle(Circle { p: Point { x: 0.0, y: 0.0 }, r: 10.0 });
+    let colliders = vec![
+	Collider::new(shape, CollisionFilter::new(0b001, 0b010), 0),
+	Collider::new(shape, CollisionFilter::new(0b010, 0b100), 1),
+	Collider::new(shape, CollisionFilter::new(0b100, 0b011), 2),
+    ];
+    // all three overlap, but only 1 and 2 let each other in
+    assert_eq!(candidate_pairs(&colliders), vec![(1, 2)]);
+    assert_eq!(colliding_pairs(&colliders), vec![(1, 2)]);
+    assert_eq!(overlapping(&colliders, &colliders[0]), Vec::<usize>::new());
+    assert_eq!(overlapping(&colliders, &colliders[1]), vec![2]);
+}
+
+proptest! {
+    #[test]
+    fn sweep_and_prune_matches_brute_force(colliders in prop::collection::vec(collider(), 0..40)) {
+	prop_assert_eq!(candidate_pairs(&colliders), brute_force_candidates(&colliders));
+	prop_assert_eq!(colliding_pairs(&colliders), brute_force_colliding(&colliders));
+    }
+}
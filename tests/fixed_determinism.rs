@@ -0,0 +1,138 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::fixed::*;
+
+mod common;
+use common::Lcg;
+
+// a Fixed in [0, n) with a random fractional part
+fn fixed(rng: &mut Lcg, n: i64) -> Fixed {
+    Fixed::from_raw((rng.bits() as i64) % (n << FRAC_BITS))
+}
+
+fn shapes(seed: u64, n: usize) -> Vec<FixedShape> {
+    let mut rng = Lcg(seed);
+    (0..n).map(|_| {
+	let p1 = FixedPoint { x: fixed(&mut rng, 1200), y: fixed(&mut rng, 800) };
+	let r = fixed(&mut rng, 60);
+	if rng.bits().is_multiple_of(3) {
+	    FixedShape::Circle(FixedCircle { p: p1, r })
+	} else {
+	    let d = FixedVector { x: fixed(&mut rng, 300) - Fixed::from_int(150), y: fixed(&mut rng, 300) - Fixed::from_int(150) };
+	    FixedShape::Stad(FixedStad { p1, p2: p1.plus(d), r })
+	}
+    }).collect()
+}
+
+// FNV-1a over every pair's outcome: whether it collides and, if so, the raw
+// bits of the contact
+fn fingerprint(shapes: &[FixedShape]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    let mut eat = |v: i64| {
+	for byte in v.to_le_bytes().iter() {
+	    hash ^= *byte as u64;
+	    hash = hash.wrapping_mul(0x100000001b3);
+	}
+    };
+    for (i, a) in shapes.iter().enumerate() {
+	for b in shapes[i+1..].iter() {
+	    eat(a.collides(*b) as i64);
+	    if let Some(c) = a.contact(*b) {
+		for v in [c.normal.x, c.normal.y, c.depth, c.point.x, c.point.y].iter() {
+		    eat(v.raw());
+		}
+	    }
+	}
+    }
+    hash
+}
+
+#[test]
+fn same_outcomes_every_run() {
+    let first = fingerprint(&shapes(7, 300));
+    for _ in 0..3 {
+	assert_eq!(fingerprint(&shapes(7, 300)), first);
+    }
+    // recorded once; any change in the fixed point math (or a platform that
+    // rounds differently) shows up here
+    assert_eq!(first, 9643815592932213176);
+}
+
+fn to_shape(s: FixedShape) -> Shape {
+    match s {
+	FixedShape::Circle(c) => Shape::Circle(Circle { p: c.p.to_point(), r: c.r.to_f64() }),
+	FixedShape::Stad(s) => Shape::Stad(Stad { p1: s.p1.to_point(), p2: s.p2.to_point(), r: s.r.to_f64() }),
+    }
+}
+
+fn radius(s: FixedShape) -> f64 {
+    match s {
+	FixedShape::Circle(c) => c.r.to_f64(),
+	FixedShape::Stad(s) => s.r.to_f64(),
+    }
+}
+
+// away from the touching case the fixed point answers are the f64 ones
+#[test]
+fn agrees_with_f64() {
+    let fixed = shapes(11, 200);
+    let float: Vec<Shape> = fixed.iter().map(|s| to_shape(*s)).collect();
+    for i in 0..fixed.len() {
+	for j in i+1..fixed.len() {
+	    let contact = float[i].contact(float[j]);
+	    let marginal = contact.is_some_and(|c| c.depth < 0.01);
+	    if !marginal {
+		assert_eq!(fixed[i].collides(fixed[j]), float[i].collides(float[j]));
+	    }
+	    if let (Some(a), Some(b)) = (fixed[i].contact(fixed[j]), contact) {
+		assert!((a.depth.to_f64() - b.depth).abs() < 0.01);
+		// when the cores cross any normal will do
+		let core_gap = radius(fixed[i]) + radius(fixed[j]) - b.depth;
+		if b.depth > 0.01 && core_gap > 0.01 {
+		    assert!(a.normal.to_vector().dot(b.normal) > 0.999);
+		}
+	    }
+	}
+    }
+}
+
+#[test]
+fn saturates_instead_of_overflowing() {
+    let max = Fixed::from_raw(i64::MAX);
+    let min = Fixed::from_raw(i64::MIN);
+    assert_eq!(max + Fixed::ONE, max);
+    assert_eq!(min - Fixed::ONE, min);
+    assert_eq!(-min, max);
+    assert_eq!(min.abs(), max);
+    assert_eq!(max * Fixed::from_int(2), max);
+    assert_eq!(max * Fixed::from_int(-2), min);
+    assert_eq!(max / Fixed::from_raw(1), max);
+    assert_eq!(min / Fixed::from_raw(1), min);
+    assert_eq!(Fixed::ONE / Fixed::ZERO, max);
+}
+
+// segments long enough that their squared lengths multiplied together are
+// far past i64, across the whole allowed range
+#[test]
+fn long_segments_out_to_max_coord() {
+    let m = MAX_COORD as f64;
+    let cases = [
+	((-m, -m, m, m), (-m, m, m, -m)),                  // crossing at the origin
+	((-m, -m, m, -m + 1.0), (-m, 0.0, m, 0.0)),        // nearly parallel, far apart
+	((-m, 10.0, 0.0, 10.0), (100.0, -m, 100.0, m)),    // an end near the other's middle
+	((m - 5000.0, m, m, m - 5000.0), (-m, -m, -m + 3.0, -m + 7.0)),
+    ];
+    for &((ax, ay, bx, by), (cx, cy, dx, dy)) in cases.iter() {
+	let float = (Segment { p1: Point { x: ax, y: ay }, p2: Point { x: bx, y: by } },
+		     Segment { p1: Point { x: cx, y: cy }, p2: Point { x: dx, y: dy } });
+	let fixed = (FixedSegment { p1: FixedPoint::from_point(float.0.p1), p2: FixedPoint::from_point(float.0.p2) },
+		     FixedSegment { p1: FixedPoint::from_point(float.1.p1), p2: FixedPoint::from_point(float.1.p2) });
+	let (pa, pb) = float.0.closest_points(float.1);
+	let (qa, qb) = fixed.0.closest_points(fixed.1);
+	// s and t have 16 fraction bits, which is about 3 units along a segment this long
+	assert!(qa.to_point().distance(pa) < 5.0, "{:?} {:?}", qa.to_point(), pa);
+	assert!(qb.to_point().distance(pb) < 5.0, "{:?} {:?}", qb.to_point(), pb);
+	let gap = fixed.0.distance_squared_to_segment(fixed.1).sqrt().to_f64();
+	assert!((gap - pa.distance(pb)).abs() < 5.0, "{} {}", gap, pa.distance(pb));
+    }
+}
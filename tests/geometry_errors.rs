@@ -0,0 +1,68 @@
+use stad_demos::geometry::*;
+
+fn p(x: f64, y: f64) -> Point {
+    Point { x, y }
+}
+
+#[test]
+fn negative_radius() {
+    assert_eq!(Circle::try_new(p(0.0, 0.0), -1.0).unwrap_err(), GeometryError::NegativeRadius(-1.0));
+    assert_eq!(Stad::try_new(p(0.0, 0.0), p(1.0, 0.0), -0.5).unwrap_err(), GeometryError::NegativeRadius(-0.5));
+    // no radius at all is fine, a point or a segment
+    assert!(Circle::try_new(p(0.0, 0.0), 0.0).is_ok());
+    assert!(Stad::try_new(p(0.0, 0.0), p(1.0, 0.0), 0.0).is_ok());
+}
+
+#[test]
+fn non_finite() {
+    for &bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY].iter() {
+	assert_eq!(Circle::try_new(p(bad, 0.0), 1.0).unwrap_err(), GeometryError::NonFinite);
+	assert_eq!(Circle::try_new(p(0.0, 0.0), bad).unwrap_err(), GeometryError::NonFinite);
+	assert_eq!(Stad::try_new(p(0.0, 0.0), p(0.0, bad), 1.0).unwrap_err(), GeometryError::NonFinite);
+	assert_eq!(Stad::try_new(p(0.0, 0.0), p(1.0, 1.0), bad).unwrap_err(), GeometryError::NonFinite);
+	assert_eq!(Vector { x: bad, y: 0.0 }.checked_normalized().unwrap_err(), GeometryError::NonFinite);
+	assert_eq!(Line::checked_from_point_point(p(bad, 0.0), p(1.0, 1.0)).unwrap_err(), GeometryError::NonFinite);
+	let polygon = vec![p(0.0, 0.0), p(1.0, 0.0), p(bad, 1.0)];
+	assert_eq!(Polygon::try_new(polygon).unwrap_err(), GeometryError::NonFinite);
+    }
+}
+
+#[test]
+fn degenerate_direction() {
+    assert_eq!(Vector { x: 0.0, y: 0.0 }.checked_normalized().unwrap_err(), GeometryError::DegenerateDirection);
+    assert_eq!(Line::checked_from_point_point(p(3.0, 4.0), p(3.0, 4.0)).unwrap_err(), GeometryError::DegenerateDirection);
+    let n = Vector { x: 3.0, y: -4.0 }.checked_normalized().unwrap();
+    assert!((n.x - 0.6).abs() < 1e-12 && (n.y + 0.8).abs() < 1e-12);
+    // equal ends make a circle-like stadium, not an error
+    assert!(Stad::try_new(p(3.0, 4.0), p(3.0, 4.0), 1.0).is_ok());
+}
+
+#[test]
+fn parallel_lines() {
+    let a = Line::from_point_point(p(0.0, 0.0), p(10.0, 5.0));
+    let b = Line::from_point_point(p(0.0, 1.0), p(10.0, 6.0));
+    assert_eq!(a.checked_intersection(b).unwrap_err(), GeometryError::ParallelLines);
+    // the same line twice has no single intersection either
+    assert_eq!(a.checked_intersection(a).unwrap_err(), GeometryError::ParallelLines);
+    let c = Line::from_point_point(p(0.0, 5.0), p(10.0, 0.0));
+    let x = a.checked_intersection(c).unwrap();
+    assert!((x.x - 5.0).abs() < 1e-9 && (x.y - 2.5).abs() < 1e-9, "{:?}", x);
+}
+
+#[test]
+fn degenerate_polygon() {
+    assert_eq!(Polygon::try_new(vec![]).unwrap_err(), GeometryError::DegeneratePolygon);
+    assert_eq!(Polygon::try_new(vec![p(0.0, 0.0), p(1.0, 1.0)]).unwrap_err(), GeometryError::DegeneratePolygon);
+    let on_a_line = vec![p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0), p(3.0, 3.0)];
+    assert_eq!(Polygon::try_new(on_a_line).unwrap_err(), GeometryError::DegeneratePolygon);
+    let all_the_same = vec![p(1.0, 1.0); 4];
+    assert_eq!(Polygon::try_new(all_the_same).unwrap_err(), GeometryError::DegeneratePolygon);
+    assert!(Polygon::try_new(vec![p(0.0, 0.0), p(1.0, 0.0), p(0.0, 1.0)]).is_ok());
+}
+
+#[test]
+fn errors_describe_themselves() {
+    assert_eq!(GeometryError::NegativeRadius(-2.0).to_string(), "negative radius -2");
+    let boxed: Box<dyn std::error::Error> = Box::new(GeometryError::ParallelLines);
+    assert_eq!(boxed.to_string(), "parallel lines have no intersection");
+}
@@ -0,0 +1,142 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use stad_demos::geometry::*;
+use stad_demos::scene::*;
+use stad_demos::demo_scenes;
+use stad_demos::raster::*;
+
+// The scenes in demo_scenes (circ_to_circ, circ_to_stad and stad_to_stad,
+// the demos with construction lines) are rendered on the CPU at half size
+// and checked against tests/golden/<name>.png. The physics demos draw
+// straight to ggez and aren't covered. Run with UPDATE_GOLDEN=1 to write new
+// goldens after a change that's meant to look different; a mismatch leaves
+// the new render next to the test binaries' scratch files to look at.
+//
+// The goldens were generated by this code (demo_scenes and the rasterizer),
+// not captured from the original demos. So they're a regression test of the
+// port and nothing more: they catch drawing that changes from here on, but
+// a construction line that was already wrong when they were made is wrong in
+// the golden too. Look at a new golden before committing it.
+
+const SCALE: f64 = 0.5;
+// Antialiased edges can come out a shade differently from one libm to the
+// next, but no pixel should be off by more than this. Anything that actually
+// moves a line changes its pixels by far more.
+const THRESHOLD: u8 = 8;
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(format!("{}.png", name))
+}
+
+fn write_png(path: &PathBuf, image: &Image) {
+    let writer = BufWriter::new(File::create(path).unwrap());
+    let mut encoder = png::Encoder::new(writer, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let bytes: Vec<u8> = image.pixels.iter().flat_map(|p| p.iter().cloned()).collect();
+    encoder.write_header().unwrap().write_image_data(&bytes).unwrap();
+}
+
+fn read_png(path: &PathBuf) -> Image {
+    let decoder = png::Decoder::new(File::open(path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let mut bytes = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut bytes).unwrap();
+    assert_eq!(info.color_type, png::ColorType::Rgba, "{:?} isn't RGBA", path);
+    Image {
+	width: info.width as usize,
+	height: info.height as usize,
+	pixels: bytes.chunks(4).map(|c| [c[0], c[1], c[2], c[3]]).collect(),
+    }
+}
+
+// `width` and `height` are the demo's window size
+fn check(name: &str, scene: &Scene, width: f64, height: f64) {
+    let image = render(scene, (width*SCALE) as usize, (height*SCALE) as usize, SCALE);
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+	write_png(&path, &image);
+	return;
+    }
+    assert!(path.exists(), "no golden image for {} (run with UPDATE_GOLDEN=1 to make one)", name);
+    let diff = image.compare(&read_png(&path), THRESHOLD).expect("golden image is the wrong size");
+    if diff.differing > 0 {
+	let actual = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.png", name));
+	write_png(&actual, &image);
+	panic!("{} differs from its golden image in {} pixels (by up to {}); see {:?}",
+	       name, diff.differing, diff.max_channel, actual);
+    }
+}
+
+fn circle(x: f64, y: f64, r: f64) -> Circle {
+    Circle { p: Point { x, y }, r }
+}
+
+#[test]
+fn circ_to_circ() {
+    // where the demo starts, then dragged into each other
+    let apart = [circle(200.0, 500.0, 95.0), circle(800.0, 300.0, 185.0)];
+    let touching = [circle(500.0, 500.0, 95.0), circle(700.0, 420.0, 185.0)];
+    for (name, circs) in [("circ_to_circ_apart", apart), ("circ_to_circ_touching", touching)].iter() {
+	let colliding = circs[0].collides_circle(circs[1]);
+	check(name, &demo_scenes::circ_to_circ(*circs, colliding), 1200.0, 1200.0);
+    }
+}
+
+#[test]
+fn circ_to_stad() {
+    let stad = Stad::new(500.0, 300.0, 200.0, 400.0, 120.0);
+    let upright = Stad::new(600.0, 200.0, 600.0, 700.0, 60.0);
+    let cases = [
+	// where the demo starts, beside the stadium
+	("circ_to_stad_beside", circle(600.0, 700.0, 70.0), stad),
+	// past an end, where the distance to the end is what counts
+	("circ_to_stad_past_end", circle(900.0, 200.0, 70.0), stad),
+	("circ_to_stad_touching", circle(480.0, 520.0, 70.0), stad),
+	// the perpendicular lines used to come from slopes, so check an upright one
+	("circ_to_stad_upright", circle(850.0, 450.0, 100.0), upright),
+	// a stadium with no length is a circle, and has no line to draw
+	("circ_to_stad_no_length", circle(700.0, 600.0, 70.0), Stad::new(400.0, 400.0, 400.0, 400.0, 90.0)),
+    ];
+    for (name, circ, stad) in cases.iter() {
+	let colliding = circ.collides_stad(*stad);
+	check(name, &demo_scenes::circ_to_stad(*circ, *stad, colliding), 1200.0, 1200.0);
+    }
+}
+
+#[test]
+fn stad_to_stad() {
+    let cases = [
+	// where the demo starts
+	("stad_to_stad_level", Stad::new(100.0, 100.0, 200.0, 100.0, 40.0),
+	 Stad::new(100.0, 300.0, 300.0, 300.0, 120.0)),
+	("stad_to_stad_crossing", Stad::new(300.0, 200.0, 900.0, 600.0, 40.0),
+	 Stad::new(300.0, 600.0, 900.0, 200.0, 60.0)),
+    ];
+    for (name, a, b) in cases.iter() {
+	let colliding = a.collides_stad(*b);
+	check(name, &demo_scenes::stad_to_stad(*a, *b, colliding, 1200.0, 800.0), 1200.0, 800.0);
+    }
+}
+
+// nothing in a scene should come out NaN, however the shapes are dragged
+#[test]
+fn circ_to_stad_with_shapes_on_top_of_each_other_is_finite() {
+    let stads = [Stad::new(400.0, 400.0, 400.0, 400.0, 90.0), Stad::new(300.0, 400.0, 500.0, 400.0, 90.0)];
+    for stad in stads.iter() {
+	for &p in [stad.p1, stad.p2, stad.p1.lerp(stad.p2, 0.5)].iter() {
+	    let scene = demo_scenes::circ_to_stad(Circle { p, r: 70.0 }, *stad, true);
+	    for primitive in scene.primitives.iter() {
+		let points = match *primitive {
+		    Primitive::Line { p1, p2, .. } | Primitive::Stad { p1, p2, .. } => vec![p1, p2],
+		    Primitive::Disc { center, .. } | Primitive::Ring { center, .. } => vec![center],
+		};
+		for q in points {
+		    assert!(q.x.is_finite() && q.y.is_finite(), "{:?} with {:?} at {:?}", primitive, stad, p);
+		}
+	    }
+	}
+    }
+}
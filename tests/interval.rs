@@ -0,0 +1,73 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::interval::*;
+use stad_demos::tolerance::*;
+
+// Right on the edge of the tolerance band the plain f64 test can go either
+// way, and the certified one has to say so rather than guess.
+
+const EPS: f64 = DEFAULT_EPSILON;
+
+fn circle(x: f64, y: f64, r: f64) -> Shape {
+    Shape::Circle(Circle { p: Point { x, y }, r })
+}
+
+// Tiny circles, but everything is EPS times a power of two, so the gap is
+// exactly EPS with no rounding anywhere in setting it up.
+fn exactly_epsilon_apart() -> (Shape, Shape) {
+    (circle(0.0, 0.0, 2.0 * EPS), circle(4.0 * EPS, 0.0, EPS))
+}
+
+#[test]
+fn gap_of_exactly_epsilon_is_uncertain() {
+    let (a, b) = exactly_epsilon_apart();
+    assert_eq!(a.collides_certified(b), Certainty::Uncertain);
+    assert_eq!(b.collides_certified(a), Certainty::Uncertain);
+}
+
+#[test]
+fn gap_within_an_ulp_of_epsilon_is_uncertain() {
+    // an ulp either way of the exact case
+    for &x in [(4.0 * EPS).next_down(), (4.0 * EPS).next_up()].iter() {
+	let (a, b) = (circle(0.0, 0.0, 2.0 * EPS), circle(x, 0.0, EPS));
+	assert_eq!(a.collides_certified(b), Certainty::Uncertain, "at {}", x);
+    }
+    // and at an everyday size, where the gap is as close to EPS as f64s
+    // around 20 can get
+    let (a, b) = (circle(0.0, 0.0, 10.0), circle(20.0 + EPS, 0.0, 10.0));
+    assert_eq!(a.collides_certified(b), Certainty::Uncertain);
+    let s = Shape::Stad(Stad::new(-50.0, 0.0, 50.0, 0.0, 10.0));
+    let t = Shape::Stad(Stad::new(-30.0, 20.0 + EPS, 70.0, 20.0 + EPS, 10.0));
+    assert_eq!(s.collides_certified(t), Certainty::Uncertain);
+}
+
+#[test]
+fn clear_cases_are_certain() {
+    let a = circle(0.0, 0.0, 10.0);
+    assert_eq!(a.collides_certified(circle(20.0, 0.0, 10.0)), Certainty::Yes);
+    assert_eq!(a.collides_certified(circle(20.0 + 1e-3, 0.0, 10.0)), Certainty::No);
+    assert_eq!(a.collides_certified(circle(15.0, 0.0, 10.0)), Certainty::Yes);
+    assert_eq!(a.collides_certified(circle(20.0 + 2.0 * EPS, 0.0, 10.0)), Certainty::No);
+}
+
+#[test]
+fn uncertain_pairs_reports_only_the_close_call() {
+    let (a, b) = exactly_epsilon_apart();
+    let colliders: Vec<Collider> = vec![
+	// overlapping
+	circle(100.0, 100.0, 10.0),
+	circle(110.0, 100.0, 10.0),
+	// the close call
+	a,
+	b,
+	// boxes overlapping, but the circles clearly apart
+	circle(200.0, 200.0, 10.0),
+	circle(215.0, 215.0, 10.0),
+	// touching exactly
+	circle(300.0, 0.0, 10.0),
+	circle(320.0, 0.0, 10.0),
+	// nowhere near
+	circle(-500.0, -500.0, 10.0),
+    ].into_iter().map(|s| Collider::new(s, CollisionFilter::default(), 0)).collect();
+    assert_eq!(uncertain_pairs(&colliders), vec![(2, 3)]);
+}
@@ -0,0 +1,98 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::dynamics::*;
+
+const DT: f64 = 1.0 / 60.0;
+
+fn slab(x: f64, y: f64) -> RigidBody {
+    RigidBody::new(Collider::stad(Stad::new(x - 40.0, y, x + 40.0, y, 20.0)), 1.0)
+}
+
+// a floor (body 0) with its top at y = 730, a stack of two on the left
+// (bodies 1 and 2) and one on its own on the right (body 3)
+fn two_piles() -> Simulation {
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    sim.add_body(RigidBody::fixed(Collider::stad(Stad::new(100.0, 750.0, 1100.0, 750.0, 20.0))));
+    sim.add_body(slab(300.0, 709.0));
+    sim.add_body(slab(300.0, 668.0));
+    sim.add_body(slab(800.0, 709.0));
+    sim
+}
+
+fn asleep(sim: &Simulation) -> Vec<bool> {
+    sim.bodies.iter().map(|b| b.asleep).collect()
+}
+
+fn settle(sim: &mut Simulation) {
+    for _ in 0..300 {
+	sim.step(DT);
+    }
+}
+
+#[test]
+fn piles_on_one_floor_are_separate_islands() {
+    let mut sim = two_piles();
+    for _ in 0..10 {
+	sim.step(DT);
+    }
+    // landed on each other, but the floor is in neither
+    assert_eq!(sim.islands, vec![vec![1, 2], vec![3]]);
+}
+
+#[test]
+fn resting_islands_fall_asleep_and_stay_put() {
+    let mut sim = two_piles();
+    settle(&mut sim);
+    assert_eq!(asleep(&sim), vec![false, true, true, true]);
+    for body in sim.bodies.iter().skip(1) {
+	assert_eq!(body.velocity, Vector { x: 0.0, y: 0.0 });
+	assert_eq!(body.angular_velocity, 0.0);
+    }
+    // asleep, nothing moves at all, gravity or not
+    let before: Vec<Point> = sim.bodies.iter().map(|b| b.position).collect();
+    settle(&mut sim);
+    let after: Vec<Point> = sim.bodies.iter().map(|b| b.position).collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn not_before_time_to_sleep() {
+    let mut sim = two_piles();
+    sim.sleep.time_to_sleep = 1000.0;
+    settle(&mut sim);
+    assert_eq!(asleep(&sim), vec![false, false, false, false]);
+}
+
+#[test]
+fn waking_one_body_wakes_its_island_only() {
+    let mut sim = two_piles();
+    settle(&mut sim);
+    sim.bodies[2].velocity = Vector { x: 0.0, y: -200.0 };
+    sim.bodies[2].wake();
+    sim.step(DT);
+    assert_eq!(asleep(&sim), vec![false, false, false, true]);
+    // and it goes back to sleep once it's landed again
+    settle(&mut sim);
+    assert_eq!(asleep(&sim), vec![false, true, true, true]);
+}
+
+#[test]
+fn something_landing_on_a_sleeping_pile_wakes_it() {
+    let mut sim = two_piles();
+    settle(&mut sim);
+    let dropped = sim.add_body(slab(800.0, 400.0));
+    let mut woke = false;
+    for _ in 0..60 {
+	sim.step(DT);
+	woke |= !sim.bodies[3].asleep;
+    }
+    assert!(woke, "the right hand pile never woke");
+    // the left hand pile was never touched
+    assert!(sim.bodies[1].asleep && sim.bodies[2].asleep);
+    settle(&mut sim);
+    assert!(sim.bodies[3].asleep && sim.bodies[dropped].asleep);
+    // resting on the pile, not sunk through it
+    assert!(sim.bodies[dropped].position.y < sim.bodies[3].position.y - 39.0,
+	    "{:?} {:?}", sim.bodies[dropped].position, sim.bodies[3].position);
+}
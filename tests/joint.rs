@@ -0,0 +1,102 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::joint::*;
+use stad_demos::dynamics::*;
+
+const DT: f64 = 1.0 / 60.0;
+
+fn ball(x: f64, y: f64, r: f64) -> Collider {
+    Collider::circle(Circle { p: Point { x, y }, r })
+}
+
+fn step(sim: &mut Simulation, steps: usize) -> f64 {
+    let mut widest: f64 = 0.0;
+    for _ in 0..steps {
+	sim.step(DT);
+	widest = widest.max(widest_gap(sim));
+    }
+    widest
+}
+
+// how far apart the worst joint's two anchors are
+fn widest_gap(sim: &Simulation) -> f64 {
+    sim.joints.iter()
+	.map(|j| {
+	    let (a, b) = j.world_anchors(&sim.bodies);
+	    a.distance(b)
+	})
+	.fold(0.0, f64::max)
+}
+
+// six balls hanging in a chain from a fixed pin at (500, 100), each pinned to
+// the next halfway between them, with a weight five times as heavy on the end
+fn chain() -> Simulation {
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    sim.sleep.enabled = false;
+    let mut prev = sim.add_body(RigidBody::fixed(ball(500.0, 100.0, 5.0)));
+    for i in 1..=6 {
+	let y = 100.0 + 40.0 * i as f64;
+	let density = if i == 6 { 5.0 } else { 1.0 };
+	let link = sim.add_body(RigidBody::new(ball(500.0, y, 10.0), density));
+	let joint = Joint::revolute(&sim.bodies, prev, link, Point { x: 500.0, y: y - 20.0 });
+	sim.add_joint(joint);
+	prev = link;
+    }
+    sim
+}
+
+#[test]
+fn revolute_anchors_stay_together_under_load() {
+    let mut sim = chain();
+    // just hanging, every link carries the weight and the links below it
+    assert!(step(&mut sim, 600) < 1.5, "anchors came {} apart", widest_gap(&sim));
+    let end = sim.bodies[6].position;
+    assert!((end.x - 500.0).abs() < 1e-6 && end.y >= 340.0 && end.y < 345.0, "{:?}", end);
+
+    // Position errors are fed back over several steps rather than fixed at
+    // once, so swinging hard stretches the chain a little...
+    sim.bodies[6].velocity = Vector { x: 300.0, y: 0.0 };
+    let swinging = step(&mut sim, 600);
+    assert!(swinging < 10.0, "anchors came {} apart swinging", swinging);
+    // ...and it pulls itself back together as it slows down
+    step(&mut sim, 1200);
+    assert!(widest_gap(&sim) < 1.5, "anchors {} apart after swinging", widest_gap(&sim));
+}
+
+#[test]
+fn distance_joint_keeps_its_length() {
+    // a pendulum 150 long let go from level with the pin
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    sim.sleep.enabled = false;
+    let pin = sim.add_body(RigidBody::fixed(ball(500.0, 100.0, 5.0)));
+    let bob = sim.add_body(RigidBody::new(ball(650.0, 100.0, 15.0), 5.0));
+    let joint = Joint::distance(&sim.bodies, pin, bob, Point { x: 500.0, y: 100.0 }, Point { x: 650.0, y: 100.0 });
+    sim.add_joint(joint);
+    let mut lowest: f64 = 0.0;
+    for _ in 0..600 {
+	sim.step(DT);
+	let (a, b) = sim.joints[0].world_anchors(&sim.bodies);
+	assert!((a.distance(b) - 150.0).abs() < 1.5, "{}", a.distance(b));
+	lowest = lowest.max(sim.bodies[bob].position.y);
+    }
+    // it did swing through the bottom
+    assert!(lowest > 245.0, "{}", lowest);
+}
+
+#[test]
+fn weld_holds_a_loaded_beam_level() {
+    // a beam welded at one end to a wall, with a ball welded to the far end
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    sim.sleep.enabled = false;
+    let wall = sim.add_body(RigidBody::fixed(Collider::stad(Stad::new(100.0, 100.0, 100.0, 300.0, 10.0))));
+    let beam = sim.add_body(RigidBody::new(Collider::stad(Stad::new(120.0, 200.0, 300.0, 200.0, 10.0)), 1.0));
+    let weight = sim.add_body(RigidBody::new(ball(340.0, 200.0, 20.0), 1.0));
+    let joint = Joint::weld(&sim.bodies, wall, beam, Point { x: 110.0, y: 200.0 });
+    sim.add_joint(joint);
+    let joint = Joint::weld(&sim.bodies, beam, weight, Point { x: 315.0, y: 200.0 });
+    sim.add_joint(joint);
+    assert!(step(&mut sim, 600) < 0.5, "anchors came {} apart", widest_gap(&sim));
+    assert!(sim.bodies[beam].angle.abs() < 0.01, "beam sagged to {}", sim.bodies[beam].angle);
+    assert!((sim.bodies[weight].angle - sim.bodies[beam].angle).abs() < 0.01);
+}
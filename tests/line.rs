@@ -0,0 +1,132 @@
+use stad_demos::geometry::*;
+
+mod common;
+use common::Lcg;
+
+const SLOP: f64 = 1e-12;
+
+// both constructors take the point first, then the vector
+#[test]
+fn from_normal_and_from_point_direction_agree() {
+    let p = Point { x: 3.0, y: -2.0 };
+    let d = Vector { x: 4.0, y: 3.0 };
+    let along = Line::from_point_direction(p, d);
+    let facing = Line::from_normal(p, d.perpendicular().scaled(-1.0));
+    for l in [along, facing].iter() {
+	assert!(l.signed_distance(p).abs() < SLOP);
+	assert!(l.signed_distance(p.plus(d)).abs() < SLOP);
+	assert!(l.direction().cross(d).abs() < SLOP);
+    }
+    assert!((along.a - facing.a).abs() < SLOP && (along.b - facing.b).abs() < SLOP && (along.c - facing.c).abs() < SLOP);
+    // the normal's side is positive
+    let n = Vector { x: 0.0, y: 2.0 };
+    let l = Line::from_normal(p, n);
+    assert!((l.signed_distance(p.plus(n)) - 2.0).abs() < SLOP);
+}
+
+#[test]
+fn perpendicular_through_a_point() {
+    let s = Segment { p1: Point { x: 0.0, y: 0.0 }, p2: Point { x: 10.0, y: 0.0 } };
+    let l = s.perpendicular_through(Point { x: 4.0, y: 7.0 });
+    assert!(l.signed_distance(Point { x: 4.0, y: -100.0 }).abs() < SLOP);
+    assert!((l.signed_distance(Point { x: 10.0, y: 0.0 }) - 6.0).abs() < SLOP);
+}
+
+// a handful of lines at different angles and offsets, including upright and
+// flat ones
+fn lines() -> Vec<(Line, Point, Vector)> {
+    let mut rng = Lcg(45);
+    let mut out = vec![
+	(Point { x: 5.0, y: -3.0 }, Vector { x: 0.0, y: 2.0 }),
+	(Point { x: -1.0, y: 4.0 }, Vector { x: -3.0, y: 0.0 }),
+    ];
+    for _ in 0..50 {
+	let p = Point { x: rng.range(-100.0, 100.0), y: rng.range(-100.0, 100.0) };
+	let d = Vector { x: rng.range(-10.0, 10.0), y: rng.range(-10.0, 10.0) };
+	out.push((p, d));
+    }
+    out.into_iter().map(|(p, d)| (Line::from_point_direction(p, d), p, d)).collect()
+}
+
+fn near(a: Point, b: Point) -> bool {
+    a.distance(b) < 1e-9
+}
+
+#[test]
+fn signed_distance_is_the_offset_along_the_normal() {
+    for (l, p, d) in lines() {
+	assert!(l.signed_distance(p).abs() < 1e-9);
+	assert!(l.signed_distance(p.plus(d.scaled(3.0))).abs() < 1e-9);
+	for &k in [-7.5, -1.0, 0.25, 12.0].iter() {
+	    let q = p.plus(d.scaled(0.5)).plus(l.normal().scaled(k));
+	    assert!((l.signed_distance(q) - k).abs() < 1e-9);
+	    assert!((l.distance_to_point(q) - k.abs()).abs() < 1e-9);
+	}
+    }
+}
+
+#[test]
+fn project_lands_on_the_line_square_to_it() {
+    let mut rng = Lcg(46);
+    for (l, p, _) in lines() {
+	let q = Point { x: rng.range(-200.0, 200.0), y: rng.range(-200.0, 200.0) };
+	let on = l.project(q);
+	assert!(l.signed_distance(on).abs() < 1e-9);
+	assert!(on.towards(q).dot(l.direction()).abs() < 1e-9);
+	assert!(near(l.project(on), on));
+	// nothing else on the line is closer
+	assert!(on.distance(q) <= p.distance(q) + 1e-9);
+    }
+}
+
+#[test]
+fn reflect_flips_the_side_and_keeps_the_distance() {
+    let mut rng = Lcg(47);
+    for (l, _, _) in lines() {
+	let q = Point { x: rng.range(-200.0, 200.0), y: rng.range(-200.0, 200.0) };
+	let r = l.reflect(q);
+	assert!((l.signed_distance(r) + l.signed_distance(q)).abs() < 1e-9);
+	assert!(near(l.project(r), l.project(q)));
+	assert!(near(l.reflect(r), q));
+    }
+}
+
+#[test]
+fn reflect_vector_flips_the_normal_part() {
+    let mut rng = Lcg(48);
+    for (l, p, _) in lines() {
+	let v = Vector { x: rng.range(-10.0, 10.0), y: rng.range(-10.0, 10.0) };
+	let r = l.reflect_vector(v);
+	assert!((r.dot(l.normal()) + v.dot(l.normal())).abs() < 1e-9);
+	assert!((r.dot(l.direction()) - v.dot(l.direction())).abs() < 1e-9);
+	// the same as reflecting both ends of it
+	let ends = l.reflect(p).towards(l.reflect(p.plus(v)));
+	assert!((ends.x - r.x).abs() < 1e-9 && (ends.y - r.y).abs() < 1e-9);
+    }
+    // bouncing off the floor
+    let floor = Line::from_normal(Point { x: 0.0, y: 10.0 }, Vector { x: 0.0, y: -1.0 });
+    let r = floor.reflect_vector(Vector { x: 3.0, y: 4.0 });
+    assert!((r.x - 3.0).abs() < SLOP && (r.y + 4.0).abs() < SLOP);
+}
+
+#[test]
+fn line_and_parametric_line_round_trip() {
+    for (l, p, d) in lines() {
+	let pl = l.to_parametric();
+	assert!(l.signed_distance(pl.origin).abs() < 1e-9);
+	assert!((pl.dir.magnitude() - 1.0).abs() < SLOP);
+	assert!(pl.dir.cross(d).abs() < 1e-9 && pl.dir.dot(d) > 0.0);
+	let back = pl.to_line();
+	assert!((back.a - l.a).abs() < SLOP && (back.b - l.b).abs() < SLOP);
+	assert!((back.c - l.c).abs() < 1e-9);
+	// and starting from the parametric side, with a dir that isn't unit
+	let pl = ParametricLine { origin: p, dir: d };
+	let l = pl.to_line();
+	for &t in [-2.0, 0.0, 0.5, 3.0].iter() {
+	    assert!(l.signed_distance(pl.at(t)).abs() < 1e-9);
+	}
+	let again = l.to_parametric();
+	assert!(again.dir.cross(pl.dir).abs() < 1e-9 && again.dir.dot(pl.dir) > 0.0);
+	assert!(near(pl.project(again.origin), again.origin));
+    }
+}
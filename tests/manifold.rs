@@ -0,0 +1,132 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::manifold::*;
+
+fn square() -> Polygon {
+    Polygon::new(vec![Point{x: 0.0, y: 0.0}, Point{x: 100.0, y: 0.0},
+		      Point{x: 100.0, y: 100.0}, Point{x: 0.0, y: 100.0}])
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+// The points of a side by side pair, sorted along x, as (x, depth, id)
+fn sorted(m: &Manifold) -> Vec<(f64, f64, u32)> {
+    let mut points: Vec<_> = m.points().iter().map(|p| (p.point.x, p.depth, p.id)).collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    points
+}
+
+#[test]
+fn parallel_stads_get_both_ends_of_the_overlap() {
+    // cores 15 apart with radii adding to 18, overlapping along x from 30
+    // (the other's p1) to 100 (self's p2)
+    let a = Stad::new(0.0, 0.0, 100.0, 0.0, 10.0);
+    let b = Stad::new(30.0, 15.0, 150.0, 15.0, 8.0);
+    let m = a.manifold_stad(b).unwrap();
+    assert!(close(m.normal.x, 0.0) && close(m.normal.y, 1.0), "{:?}", m.normal);
+    let points = sorted(&m);
+    assert_eq!(points.len(), 2);
+    assert_eq!((points[0].2, points[1].2), (2, 1));
+    for (&(x, depth, _), &want) in points.iter().zip([30.0, 100.0].iter()) {
+	assert!(close(x, want) && close(depth, 3.0), "{:?}", points);
+    }
+    // halfway between a's surface (y = 10) and b's (y = 7)
+    assert!(m.points().iter().all(|p| close(p.point.y, 8.5)), "{:?}", m);
+
+    // the other way round b is the reference, so the ends are named from
+    // its side: its own p1 (0) and a's p2 (3)
+    let r = b.manifold_stad(a).unwrap();
+    assert!(close(r.normal.y, -1.0));
+    let ids: Vec<u32> = sorted(&r).iter().map(|p| p.2).collect();
+    assert_eq!(ids, vec![0, 3]);
+    // and Shape goes the same way
+    assert_eq!(Shape::Stad(a).manifold(Shape::Stad(b)), Some(m));
+}
+
+#[test]
+fn slightly_tilted_stads_get_the_depth_at_each_end() {
+    // b climbs 3 over its 120, well inside the parallel tolerance, so its
+    // core is 14 from a's at x = 30 and 14 + 3*70/120 at x = 100
+    let a = Stad::new(0.0, 0.0, 100.0, 0.0, 10.0);
+    let b = Stad::new(30.0, 14.0, 150.0, 17.0, 8.0);
+    let points = sorted(&a.manifold_stad(b).unwrap());
+    assert_eq!(points.len(), 2);
+    assert_eq!((points[0].2, points[1].2), (2, 1));
+    assert!(close(points[0].1, 18.0 - 14.0), "{:?}", points);
+    assert!(close(points[1].1, 18.0 - (14.0 + 3.0*70.0/120.0)), "{:?}", points);
+}
+
+#[test]
+fn manifold_ids_survive_a_small_move() {
+    // what warm starting relies on: nudged a little from one step to the
+    // next, the same ends come back under the same ids
+    let a = Stad::new(0.0, 0.0, 100.0, 0.0, 10.0);
+    let b = Stad::new(30.0, 15.0, 150.0, 15.0, 8.0);
+    let before = sorted(&a.manifold_stad(b).unwrap());
+    for &(dx, dy) in [(0.5, 0.0), (-0.7, 0.3), (2.0, -0.4), (0.0, 1.0)].iter() {
+	let moved = b.translated(Vector { x: dx, y: dy });
+	let after = sorted(&a.manifold_stad(moved).unwrap());
+	assert_eq!(after.len(), 2);
+	assert_eq!((after[0].2, after[1].2), (before[0].2, before[1].2), "moved by ({}, {})", dx, dy);
+	// and the depths follow the move
+	for p in after.iter() {
+	    assert!(close(p.1, 3.0 - dy), "{:?}", after);
+	}
+	assert!(close(after[0].0, 30.0 + dx) && close(after[1].0, 100.0));
+    }
+}
+
+#[test]
+fn stad_flat_on_a_polygon_edge_gets_both_ends() {
+    // lying along the top edge (y = 0), sunk 1 into it
+    let s = Stad::new(20.0, -9.0, 80.0, -9.0, 10.0);
+    let m = s.manifold_polygon(&square()).unwrap();
+    assert!(close(m.normal.x, 0.0) && close(m.normal.y, 1.0), "{:?}", m.normal);
+    let mut points = m.points().to_vec();
+    points.sort_by(|a, b| a.point.x.total_cmp(&b.point.x));
+    assert_eq!(points.len(), 2);
+    // edge 0 is the reference, and the ends come from the stad's p1 and p2
+    assert_eq!((points[0].id, points[1].id), (2, 3));
+    for (p, x) in points.iter().zip([20.0, 80.0].iter()) {
+	assert!(close(p.depth, 1.0), "{:?}", p);
+	assert!(close(p.point.x, *x) && close(p.point.y, 0.5), "{:?}", p);
+    }
+    // the same from the other winding
+    let mut reversed = square();
+    reversed.points.reverse();
+    let r = s.manifold_polygon(&reversed).unwrap();
+    assert_eq!(r.points().len(), 2);
+    assert!(close(r.normal.y, 1.0));
+}
+
+#[test]
+fn stad_against_a_polygon_corner_gets_one_point() {
+    let s = Stad::new(110.0, -20.0, 150.0, -60.0, 25.0);
+    let m = s.manifold_polygon(&square()).unwrap();
+    assert_eq!(m.points().len(), 1);
+    let p = m.points()[0];
+    assert_eq!(p.id, SINGLE_POINT);
+    let dist = (10.0f64*10.0 + 20.0*20.0).sqrt();
+    assert!(close(p.depth, 25.0 - dist), "{:?}", p);
+    // from the core's nearest point towards the corner
+    assert!(close(m.normal.x, -10.0/dist) && close(m.normal.y, 20.0/dist), "{:?}", m.normal);
+}
+
+#[test]
+fn stad_clear_of_a_polygon_has_no_manifold() {
+    assert!(Stad::new(20.0, -11.0, 80.0, -11.0, 10.0).manifold_polygon(&square()).is_none());
+    assert!(Stad::new(110.0, -20.0, 150.0, -60.0, 20.0).manifold_polygon(&square()).is_none());
+}
+
+#[test]
+fn stad_core_inside_a_polygon_is_pushed_out_the_nearest_side() {
+    // nearest the left side (x = 0)
+    let s = Stad::new(10.0, 40.0, 10.0, 60.0, 5.0);
+    let m = s.manifold_polygon(&square()).unwrap();
+    assert!(close(m.normal.x, 1.0) && close(m.normal.y, 0.0), "{:?}", m.normal);
+    for p in m.points() {
+	assert!(close(p.depth, 15.0), "{:?}", p);
+    }
+}
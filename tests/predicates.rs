@@ -0,0 +1,128 @@
+use stad_demos::geometry::*;
+use stad_demos::predicates::*;
+
+// The points here all sit on a grid of 2^-53 (an ulp of 0.5), so scaled up
+// by 2^53 they're integers and the determinants can be worked out exactly in
+// i128 to check the predicates against.
+
+const ULP: f64 = f64::EPSILON / 2.0;
+
+fn scaled(v: f64) -> i128 {
+    let s = v * (1u64 << 53) as f64;
+    assert_eq!(s.fract(), 0.0, "{} isn't on the grid", v);
+    s as i128
+}
+
+fn exact_orient(a: Point, b: Point, c: Point) -> f64 {
+    let (ax, ay, bx, by, cx, cy) = (scaled(a.x), scaled(a.y), scaled(b.x), scaled(b.y), scaled(c.x), scaled(c.y));
+    ((ax - cx)*(by - cy) - (ay - cy)*(bx - cx)).signum() as f64
+}
+
+fn exact_dot(a: Point, b: Point, c: Point) -> f64 {
+    let (ax, ay, bx, by, cx, cy) = (scaled(a.x), scaled(a.y), scaled(b.x), scaled(b.y), scaled(c.x), scaled(c.y));
+    ((bx - ax)*(cx - ax) + (by - ay)*(cy - ay)).signum() as f64
+}
+
+fn sign(v: f64) -> f64 {
+    if v == 0.0 { 0.0 } else { v.signum() }
+}
+
+// Shewchuk's example: p a few ulps either way of (0.5, 0.5), against two
+// points on y = x. The true sign is just which side of y = x p is on.
+fn near_diagonal() -> Vec<Point> {
+    let mut points = vec![];
+    for i in -32..32 {
+	for j in -32..32 {
+	    points.push(Point { x: 0.5 + i as f64 * ULP, y: 0.5 + j as f64 * ULP });
+	}
+    }
+    points
+}
+
+#[test]
+fn orient2d_is_exact_near_a_line() {
+    let (q, r) = (Point { x: 12.0, y: 12.0 }, Point { x: 24.0, y: 24.0 });
+    let mut fast_path_wrong = 0;
+    for p in near_diagonal() {
+	let naive = (p.x - r.x)*(q.y - r.y) - (p.y - r.y)*(q.x - r.x);
+	let exact = exact_orient(p, q, r);
+	fast_path_wrong += (sign(naive) != exact) as usize;
+	assert_eq!(sign(orient2d(p, q, r)), exact, "{:?}", p);
+	// the exact determinant is 12 (p.y - p.x)
+	assert_eq!(exact, sign(p.y - p.x), "{:?}", p);
+    }
+    // or the test never got past the fast path
+    assert!(fast_path_wrong > 100, "plain f64 only got {} wrong", fast_path_wrong);
+}
+
+#[test]
+fn orient2d_is_zero_for_collinear_points() {
+    let cases = [
+	(Point { x: 0.5, y: 0.5 }, Point { x: 12.0, y: 12.0 }, Point { x: 24.0, y: 24.0 }),
+	(Point { x: 0.5 + 7.0*ULP, y: 0.5 + 7.0*ULP }, Point { x: 12.0, y: 12.0 }, Point { x: 24.0, y: 24.0 }),
+	(Point { x: 1e-3, y: 3e-3 }, Point { x: 2e-3, y: 6e-3 }, Point { x: 0.0, y: 0.0 }),
+	(Point { x: -1e15, y: 1.0 }, Point { x: 1e15, y: 1.0 }, Point { x: 0.25, y: 1.0 }),
+	(Point { x: 3.0, y: 3.0 }, Point { x: 3.0, y: 3.0 }, Point { x: 7.0, y: -2.0 }),
+    ];
+    for &(a, b, c) in cases.iter() {
+	assert_eq!(orient2d(a, b, c), 0.0, "{:?} {:?} {:?}", a, b, c);
+	assert_eq!(orient2d(b, c, a), 0.0);
+	assert_eq!(orient2d(c, b, a), 0.0);
+    }
+}
+
+#[test]
+fn orient2d_swaps_sign_with_the_order() {
+    let (q, r) = (Point { x: 12.0, y: 12.0 }, Point { x: 24.0, y: 24.0 });
+    for p in near_diagonal() {
+	assert_eq!(sign(orient2d(p, q, r)), -sign(orient2d(q, p, r)));
+	assert_eq!(sign(orient2d(p, q, r)), sign(orient2d(q, r, p)));
+    }
+}
+
+#[test]
+fn dot2d_is_exact_near_a_right_angle() {
+    // (b - a) and (c - a) are exactly at right angles for a = (0.5, 0.5)
+    let (b, c) = (Point { x: 12.0, y: 12.0 }, Point { x: -11.0, y: 12.0 });
+    let mut fast_path_wrong = 0;
+    for a in near_diagonal() {
+	let naive = (b.x - a.x)*(c.x - a.x) + (b.y - a.y)*(c.y - a.y);
+	let exact = exact_dot(a, b, c);
+	fast_path_wrong += (sign(naive) != exact) as usize;
+	assert_eq!(sign(dot2d(a, b, c)), exact, "{:?}", a);
+    }
+    assert!(fast_path_wrong > 0, "plain f64 never got it wrong");
+    assert_eq!(dot2d(Point { x: 0.5, y: 0.5 }, b, c), 0.0);
+}
+
+#[test]
+fn line_side_is_zero_on_the_line() {
+    let l = Line::from_point_point(Point { x: 1.0, y: 0.0 }, Point { x: 1.0, y: 10.0 });
+    assert_eq!(line_side(l, Point { x: 1.0, y: -1e12 }), 0.0);
+    assert_eq!(line_side(l, Point { x: 1.0 + f64::EPSILON, y: 3.0 }), 1.0);
+    assert_eq!(line_side(l, Point { x: 1.0 - ULP, y: 3.0 }), -1.0);
+}
+
+// The old test compared y against the line's y at that x, which is
+// infinite or NaN for a vertical line.
+#[test]
+fn above_works_for_vertical_lines() {
+    // going down the screen (+y), so left (with y up) is smaller x
+    let down = Line::from_point_point(Point { x: 5.0, y: 0.0 }, Point { x: 5.0, y: 10.0 });
+    assert!(Point { x: 4.0, y: 3.0 }.above(down));
+    assert!(Point { x: 4.999, y: -300.0 }.above(down));
+    assert!(!Point { x: 6.0, y: 3.0 }.above(down));
+    // on the line is never above, anywhere along it
+    assert!(!Point { x: 5.0, y: 3.0 }.above(down));
+    assert!(!Point { x: 5.0, y: 1e9 }.above(down));
+    // built the other way round the sides swap
+    let up = Line::from_point_point(Point { x: 5.0, y: 10.0 }, Point { x: 5.0, y: 0.0 });
+    assert!(Point { x: 6.0, y: 3.0 }.above(up));
+    assert!(!Point { x: 4.0, y: 3.0 }.above(up));
+    assert!(!Point { x: 5.0, y: 3.0 }.above(up));
+    // and a level line built left to right has the larger y side above
+    let level = Line::from_point_point(Point { x: 0.0, y: 5.0 }, Point { x: 10.0, y: 5.0 });
+    assert!(Point { x: 3.0, y: 6.0 }.above(level));
+    assert!(!Point { x: 3.0, y: 4.0 }.above(level));
+    assert!(!Point { x: 3.0, y: 5.0 }.above(level));
+}
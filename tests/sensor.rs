@@ -0,0 +1,76 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::dynamics::*;
+use stad_demos::sensor::*;
+
+use SensorEventKind::*;
+
+const DT: f64 = 1.0 / 60.0;
+
+fn ball(x: f64, y: f64) -> Collider {
+    Collider::circle(Circle { p: Point { x, y }, r: 10.0 })
+}
+
+// a fixed sensor band across y = 400..440 (body 0) and a ball above it (body 1)
+fn falling_through() -> Simulation {
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    let band = Shape::Stad(Stad::new(0.0, 420.0, 1000.0, 420.0, 20.0));
+    sim.add_body(RigidBody::fixed(Collider::sensor(band, CollisionFilter::default(), 0)));
+    sim.add_body(RigidBody::new(ball(500.0, 300.0), 1.0));
+    sim
+}
+
+#[test]
+fn enter_then_stay_then_exit() {
+    let mut sim = falling_through();
+    let mut seen = vec![];
+    for _ in 0..120 {
+	sim.step(DT);
+	for e in sim.sensor_events.iter() {
+	    assert_eq!((e.sensor, e.other), (0, 1));
+	    seen.push(e.kind);
+	}
+    }
+    // one Enter, some Stays, one Exit, in that order, and then nothing
+    assert_eq!(seen.first(), Some(&Enter));
+    assert_eq!(seen.last(), Some(&Exit));
+    assert!(seen.len() > 2, "{:?}", seen);
+    assert!(seen[1..seen.len() - 1].iter().all(|&k| k == Stay), "{:?}", seen);
+    // it fell straight through without being slowed down
+    assert!(sim.bodies[1].position.y > 1000.0);
+    assert!(!sim.sensors.is_overlapping(0, 1));
+    // and the band, being on a fixed body, never moved
+    assert_eq!(sim.bodies[0].position, Point { x: 500.0, y: 420.0 });
+}
+
+#[test]
+fn each_step_is_enters_and_stays_then_exits_each_sorted() {
+    let mut tracker = SensorTracker::new();
+    let first = tracker.update(vec![(2, 7), (1, 5), (1, 3)]);
+    let kinds: Vec<_> = first.iter().map(|e| (e.kind, e.sensor, e.other)).collect();
+    assert_eq!(kinds, vec![(Enter, 1, 3), (Enter, 1, 5), (Enter, 2, 7)]);
+
+    let second = tracker.update(vec![(2, 7), (0, 9), (1, 5)]);
+    let kinds: Vec<_> = second.iter().map(|e| (e.kind, e.sensor, e.other)).collect();
+    assert_eq!(kinds, vec![(Enter, 0, 9), (Stay, 1, 5), (Stay, 2, 7), (Exit, 1, 3)]);
+
+    let third = tracker.update(vec![]);
+    let kinds: Vec<_> = third.iter().map(|e| (e.kind, e.sensor, e.other)).collect();
+    assert_eq!(kinds, vec![(Exit, 0, 9), (Exit, 1, 5), (Exit, 2, 7)]);
+    assert!(tracker.update(vec![]).is_empty());
+}
+
+#[test]
+fn a_sensor_on_a_moving_body_falls_too() {
+    // only contacts skip sensors, so this one falls through the floor
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 900.0 });
+    sim.add_body(RigidBody::fixed(Collider::stad(Stad::new(0.0, 750.0, 1000.0, 750.0, 20.0))));
+    let shape = Shape::Circle(Circle { p: Point { x: 500.0, y: 600.0 }, r: 10.0 });
+    let sensor = sim.add_body(RigidBody::new(Collider::sensor(shape, CollisionFilter::default(), 0), 1.0));
+    for _ in 0..60 {
+	sim.step(DT);
+	assert!(sim.contacts.is_empty());
+    }
+    assert!(sim.bodies[sensor].position.y > 750.0);
+}
@@ -0,0 +1,52 @@
+use stad_demos::geometry::*;
+use stad_demos::simd::*;
+
+mod common;
+use common::Lcg;
+
+// The batch kernel works on squared distances and the scalar path on plain
+// ones, so they're only expected to agree up to rounding. Pairs whose gap is
+// within SLOP of touching can come out either way.
+const SLOP: f64 = 1e-9;
+
+#[test]
+fn matches_scalar_path() {
+    let mut rng = Lcg(28);
+    // not a multiple of LANES, so the padded last group is exercised too
+    let stads: Vec<Stad> = (0..LANES*50 + 3).map(|_| rng.stad(-100.0, 100.0, 20.0)).collect();
+    let batch = StadBatch::from_stads(&stads);
+    let mut d2 = vec![];
+    for _ in 0..50 {
+	let s = rng.stad(-100.0, 100.0, 20.0);
+	batch.segment_distances_squared(s, &mut d2);
+	let hits = batch.collides_stad(s);
+	assert_eq!(d2.len(), stads.len());
+	assert_eq!(hits.len(), stads.len());
+	for (i, other) in stads.iter().enumerate() {
+	    let scalar = s.segment().distance_to_segment(other.segment());
+	    assert!((d2[i].sqrt() - scalar).abs() <= SLOP * (1.0 + scalar),
+		    "{:?} to {:?}: batch {} scalar {}", s, other, d2[i].sqrt(), scalar);
+	    let gap = scalar - (s.r + other.r);
+	    if gap.abs() > SLOP {
+		assert_eq!(hits[i], s.collides_stad(*other), "{:?} and {:?}", s, other);
+	    }
+	}
+    }
+}
+
+#[test]
+fn colliding_pairs_in_batch_order() {
+    let mut rng = Lcg(4);
+    let a = StadBatch::from_stads(&(0..9).map(|_| rng.stad(-100.0, 100.0, 20.0)).collect::<Vec<_>>());
+    let b = StadBatch::from_stads(&(0..13).map(|_| rng.stad(-100.0, 100.0, 20.0)).collect::<Vec<_>>());
+    let mut expected = vec![];
+    for i in 0..a.len() {
+	for j in 0..b.len() {
+	    if a.get(i).collides_stad(b.get(j)) {
+		expected.push((i, j));
+	    }
+	}
+    }
+    assert!(!expected.is_empty());
+    assert_eq!(a.colliding_pairs(&b), expected);
+}
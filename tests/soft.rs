@@ -0,0 +1,115 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::static_geometry::*;
+use stad_demos::soft::*;
+
+// y is down, as on screen, with the demo's gravity and frame rate. The
+// floor's top is at y = 700.
+const DT: f64 = 1.0 / 60.0;
+const GRAVITY: Vector = Vector { x: 0.0, y: 900.0 };
+const FLOOR: f64 = 700.0;
+
+fn floor() -> StaticGeometry {
+    StaticGeometry::new(&[Stad::new(0.0, 720.0, 1200.0, 720.0, 20.0)], CollisionFilter::default())
+}
+
+fn row(x: f64, y: f64, n: usize, spacing: f64) -> Vec<Point> {
+    (0..n).map(|i| Point { x: x + i as f64 * spacing, y }).collect()
+}
+
+// the worst stretch or squash of any link, as a fraction of its rest length
+fn link_error(c: &SoftChain) -> f64 {
+    c.links.iter()
+	.map(|l| (c.particles[l.a].position.distance(c.particles[l.b].position) - l.rest).abs() / l.rest)
+	.fold(0.0, f64::max)
+}
+
+// how far the fastest particle moved in the last step
+fn fastest(c: &SoftChain) -> f64 {
+    c.particles.iter().map(|p| p.previous.distance(p.position)).fold(0.0, f64::max)
+}
+
+// the bottom of the lowest segment
+fn bottom(c: &SoftChain) -> f64 {
+    c.particles.iter().map(|p| p.position.y + c.radius).fold(f64::MIN, f64::max)
+}
+
+#[test]
+fn rope_links_keep_their_length() {
+    // pinned at one end and let go level, so it swings down and hangs
+    let mut rope = SoftChain::rope(&row(300.0, 200.0, 12, 20.0), 5.0);
+    rope.particles[0].inv_mass = 0.0;
+    let mut sim = SoftSimulation::new(GRAVITY);
+    sim.add_chain(rope);
+    let nothing = StaticGeometry::new(&[], CollisionFilter::default());
+    for n in 0..600 {
+	sim.step(&nothing, DT);
+	// a few iterations a step can't make them exact while it swings
+	assert!(link_error(&sim.chains[0]) < 0.05, "step {}: {}", n, link_error(&sim.chains[0]));
+    }
+    // hanging down from the pin by now, still swaying a little
+    let rope = &sim.chains[0];
+    assert_eq!(rope.particles[0].position, Point { x: 300.0, y: 200.0 });
+    let end = rope.particles[11].position;
+    assert!((end.x - 300.0).abs() < 20.0 && end.y > 200.0 + 11.0 * 20.0 * 0.95, "{:?}", end);
+}
+
+#[test]
+fn blob_keeps_its_area() {
+    let mut sim = SoftSimulation::new(GRAVITY);
+    sim.add_chain(SoftChain::blob(Point { x: 400.0, y: 400.0 }, 60.0, 16, 10.0));
+    let rest = sim.chains[0].rest_area;
+    assert!(rest > 0.0);
+    let walls = floor();
+    for n in 0..600 {
+	sim.step(&walls, DT);
+	// squashed on landing, but never by more than this
+	let ratio = sim.chains[0].area() / rest;
+	assert!((ratio - 1.0).abs() < 0.05, "step {}: area is {} of rest", n, ratio);
+    }
+}
+
+#[test]
+fn rope_comes_to_rest_on_the_floor() {
+    let mut sim = SoftSimulation::new(GRAVITY);
+    sim.add_chain(SoftChain::rope(&row(300.0, 500.0, 12, 20.0), 5.0));
+    let walls = floor();
+    for n in 0..600 {
+	sim.step(&walls, DT);
+	assert!(bottom(&sim.chains[0]) <= FLOOR + 0.01, "step {}: sank to {}", n, bottom(&sim.chains[0]));
+    }
+    let rope = &sim.chains[0];
+    assert!((bottom(rope) - FLOOR).abs() < 0.01, "{}", bottom(rope));
+    assert!(fastest(rope) < 0.01, "still moving {} a step", fastest(rope));
+    // lying flat, not bunched up
+    assert!(link_error(rope) < 0.001);
+}
+
+#[test]
+fn blobs_dropped_on_each_other_do_not_overlap() {
+    let mut sim = SoftSimulation::new(GRAVITY);
+    sim.add_chain(SoftChain::blob(Point { x: 400.0, y: 600.0 }, 50.0, 16, 8.0));
+    sim.add_chain(SoftChain::blob(Point { x: 410.0, y: 420.0 }, 50.0, 16, 8.0));
+    let walls = floor();
+    let mut touching_steps = 0;
+    for n in 0..900 {
+	sim.step(&walls, DT);
+	let (a, b) = (&sim.chains[0], &sim.chains[1]);
+	let deepest = a.segments()
+	    .flat_map(|sa| b.segments().filter_map(move |sb| sa.contact_stad(sb)))
+	    .map(|c| c.depth)
+	    .fold(0.0, f64::max);
+	// the segments are 16 thick, so this is well short of one through another
+	assert!(deepest < 0.1, "step {}: {} deep", n, deepest);
+	touching_steps += (deepest > 0.0) as usize;
+	// one squeezed into the floor by the other gets pushed out over a few
+	// steps rather than all at once
+	assert!(bottom(a).max(bottom(b)) <= FLOOR + 0.5, "step {}: sank to {}", n, bottom(a).max(bottom(b)));
+    }
+    // or it passes without them ever meeting
+    assert!(touching_steps > 10, "only touched for {} steps", touching_steps);
+    for c in sim.chains.iter() {
+	assert!(fastest(c) < 0.01, "still moving {} a step", fastest(c));
+	assert!(bottom(c) <= FLOOR + 0.01, "{}", bottom(c));
+    }
+}
@@ -0,0 +1,144 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::broadphase::Aabb;
+use stad_demos::query::Ray;
+use stad_demos::static_geometry::StaticGeometry;
+
+mod common;
+use common::Lcg;
+
+// Every query on the quadtree has to find exactly what scanning all the walls
+// would. The levels are built to reach each part of the tree: plenty of
+// walls so nodes split, long walls across the midlines that stay in inner
+// nodes, and a tight cluster that keeps splitting until the depth limit.
+
+// scattered short walls over a 1000 square
+fn scattered(rng: &mut Lcg, n: usize) -> Vec<Stad> {
+    (0..n).map(|_| {
+	let p1 = Point { x: rng.range(0.0, 1000.0), y: rng.range(0.0, 1000.0) };
+	let d = Vector { x: rng.range(-30.0, 30.0), y: rng.range(-30.0, 30.0) };
+	Stad { p1, p2: p1.plus(d), r: rng.range(1.0, 6.0) }
+    }).collect()
+}
+
+// long walls through the middle and the quarter lines, which no quadrant
+// below the root (or the first level) can hold
+fn straddling() -> Vec<Stad> {
+    let mut walls = vec![];
+    for &x in [250.0, 500.0, 750.0].iter() {
+	walls.push(Stad::new(x, 0.0, x, 1000.0, 3.0));
+	walls.push(Stad::new(0.0, x, 1000.0, x, 3.0));
+    }
+    walls.push(Stad::new(0.0, 0.0, 1000.0, 1000.0, 2.0));
+    walls.push(Stad::new(480.0, 520.0, 520.0, 480.0, 2.0));
+    walls
+}
+
+// far more than a leaf holds, all inside a square one unit across in a level
+// thousands of units wide, so halving never separates them
+fn cluster(rng: &mut Lcg, n: usize) -> Vec<Stad> {
+    let mut walls: Vec<Stad> = (0..n).map(|_| {
+	let p1 = Point { x: rng.range(1.2, 1.4), y: rng.range(1.2, 1.4) };
+	Stad { p1, p2: p1.plus(Vector { x: rng.range(0.0, 0.1), y: rng.range(0.0, 0.1) }), r: 0.01 }
+    }).collect();
+    walls.push(Stad::new(4000.0, 4000.0, 4090.0, 4090.0, 1.0));
+    walls
+}
+
+// each level with the range queries are aimed over, so the cluster's get a
+// look in too
+fn levels() -> Vec<(Vec<Stad>, f64, f64)> {
+    let mut rng = Lcg(30);
+    let mut mixed = scattered(&mut rng, 300);
+    mixed.extend(straddling());
+    vec![
+	(scattered(&mut rng, 5), -20.0, 1020.0),
+	(scattered(&mut rng, 400), -20.0, 1020.0),
+	(straddling(), -20.0, 1020.0),
+	(mixed, -20.0, 1020.0),
+	(cluster(&mut rng, 60), 1.0, 1.6),
+    ]
+}
+
+fn probe_area(rng: &mut Lcg, lo: f64, hi: f64) -> Aabb {
+    let (x, y) = (rng.range(lo, hi), rng.range(lo, hi));
+    let (w, h) = (rng.next() * (hi - lo) / 4.0, rng.next() * (hi - lo) / 4.0);
+    Aabb { min: Point { x, y }, max: Point { x: x + w, y: y + h } }
+}
+
+#[test]
+fn query_aabb_matches_a_scan() {
+    let mut rng = Lcg(1);
+    for (walls, lo, hi) in levels() {
+	let geo = StaticGeometry::new(&walls, CollisionFilter::default());
+	for _ in 0..300 {
+	    let area = probe_area(&mut rng, lo, hi);
+	    let mut found = vec![];
+	    geo.query_aabb(area, &mut found);
+	    found.sort_unstable();
+	    let expected: Vec<usize> = (0..walls.len()).filter(|&w| walls[w].aabb().overlaps(area)).collect();
+	    assert_eq!(found, expected, "{} walls, area {:?}", walls.len(), area);
+	}
+	// the whole level finds every wall, each once
+	let mut all = vec![];
+	geo.query_aabb(Aabb { min: Point { x: -1e6, y: -1e6 }, max: Point { x: 1e6, y: 1e6 } }, &mut all);
+	all.sort_unstable();
+	assert_eq!(all, (0..walls.len()).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn overlapping_matches_a_scan() {
+    let mut rng = Lcg(2);
+    for (walls, lo, hi) in levels() {
+	let geo = StaticGeometry::new(&walls, CollisionFilter::default());
+	let scale = (hi - lo) / 20.0;
+	for _ in 0..300 {
+	    let shape = rng.shape(lo, hi, scale);
+	    let shape = match shape {
+		// keep stadiums short, so they're a probe and not another wall
+		Shape::Stad(s) => Shape::Stad(Stad { p2: s.p1.lerp(s.p2, 0.05), ..s }),
+		circle => circle,
+	    };
+	    let collider = Collider::new(shape, CollisionFilter::default(), 0);
+	    let expected: Vec<usize> = (0..walls.len())
+		.filter(|&w| shape.collides(Shape::Stad(walls[w])))
+		.collect();
+	    assert_eq!(geo.overlapping(&collider), expected, "{:?}", shape);
+	}
+    }
+}
+
+#[test]
+fn overlapping_checks_the_filter() {
+    let geo = StaticGeometry::new(&straddling(), CollisionFilter::new(2, 2));
+    let on_wall = Shape::Circle(Circle { p: Point { x: 500.0, y: 100.0 }, r: 10.0 });
+    assert!(geo.overlapping(&Collider::new(on_wall, CollisionFilter::default(), 0)).is_empty());
+    assert_eq!(geo.overlapping(&Collider::new(on_wall, CollisionFilter::new(2, 2), 0)), vec![2]);
+}
+
+#[test]
+fn raycast_matches_the_nearest_of_a_scan() {
+    let mut rng = Lcg(3);
+    for (walls, lo, hi) in levels() {
+	let geo = StaticGeometry::new(&walls, CollisionFilter::default());
+	let mut hits = 0;
+	for _ in 0..300 {
+	    let angle = rng.range(0.0, 2.0 * std::f64::consts::PI);
+	    let ray = Ray {
+		origin: Point { x: rng.range(lo, hi), y: rng.range(lo, hi) },
+		dir: Vector { x: angle.cos(), y: angle.sin() },
+	    };
+	    let max_t = rng.next() * (hi - lo);
+	    let expected = (0..walls.len())
+		.filter_map(|w| walls[w].raycast(ray).map(|t| (w, t)))
+		.filter(|&(_, t)| t <= max_t)
+		.min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+	    let found = geo.raycast(ray, max_t, CollisionFilter::default());
+	    assert_eq!(found, expected, "{:?} to {}", ray, max_t);
+	    hits += found.is_some() as usize;
+	}
+	// or it could pass by never hitting anything
+	assert!(hits > 0, "{} walls: no hits", walls.len());
+    }
+}
@@ -0,0 +1,78 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::body::*;
+use stad_demos::dynamics::*;
+use stad_demos::stepper::*;
+
+// a power of two so the sums below come out exact
+const DT: f64 = 1.0 / 64.0;
+
+fn steps(t: &mut FixedTimestep, elapsed: f64) -> usize {
+    t.advance(elapsed, |dt| assert_eq!(dt, DT))
+}
+
+#[test]
+fn whole_steps_and_the_rest_carried_over() {
+    let mut t = FixedTimestep::new(DT);
+    assert_eq!(t.alpha(), 0.0);
+    assert_eq!(steps(&mut t, DT * 2.5), 2);
+    assert_eq!(t.alpha(), 0.5);
+    assert_eq!(steps(&mut t, DT * 0.25), 0);
+    assert_eq!(t.alpha(), 0.75);
+    // the leftover quarters add up to another step
+    assert_eq!(steps(&mut t, DT * 0.25), 1);
+    assert_eq!(t.alpha(), 0.0);
+    assert_eq!(steps(&mut t, 0.0), 0);
+}
+
+#[test]
+fn any_frame_rate_gives_the_same_number_of_steps() {
+    for &frame in [1.0 / 30.0, 1.0 / 60.0, 1.0 / 144.0, 0.07].iter() {
+	let mut t = FixedTimestep::new(DT);
+	let frames = (4.0 / frame) as usize;
+	let total: usize = (0..frames).map(|_| steps(&mut t, frame)).sum();
+	let expected = (frames as f64 * frame / DT) as usize;
+	assert!(total == expected || total + 1 == expected, "{} at {}", total, frame);
+	assert!((0.0..1.0).contains(&t.alpha()));
+    }
+}
+
+#[test]
+fn slow_frames_are_clamped_to_max_steps() {
+    let mut t = FixedTimestep::new(DT);
+    t.max_steps = 3;
+    // a one second hitch runs three steps and drops the rest, bar one step's worth
+    assert_eq!(steps(&mut t, 1.0), 3);
+    assert_eq!(t.alpha(), 1.0);
+    assert_eq!(steps(&mut t, 0.0), 1);
+    assert_eq!(t.alpha(), 0.0);
+}
+
+#[test]
+fn dt_that_is_not_above_zero_never_steps() {
+    for &dt in [0.0, -DT, f64::NAN].iter() {
+	let mut t = FixedTimestep::new(dt);
+	assert_eq!(t.advance(1.0, |_| panic!("stepped with dt {}", dt)), 0);
+	assert_eq!(t.alpha(), 0.0);
+    }
+}
+
+#[test]
+fn clock_draws_bodies_between_steps() {
+    let mut sim = Simulation::new(Vector { x: 0.0, y: 0.0 });
+    let mut body = RigidBody::new(Collider::circle(Circle { p: Point { x: 0.0, y: 0.0 }, r: 10.0 }), 1.0);
+    body.velocity = Vector { x: 64.0, y: 0.0 };
+    sim.sleep.enabled = false;
+    sim.add_body(body);
+    let mut clock = SimulationClock::new(DT);
+    // not stepped yet, so drawn where it is
+    let center = |shape: Shape| match shape {
+	Shape::Circle(c) => c.p,
+	_ => unreachable!(),
+    };
+    assert_eq!(center(clock.shape(&sim, 0)), Point { x: 0.0, y: 0.0 });
+    // two steps of one unit each and half way into the third
+    assert_eq!(clock.update(&mut sim, DT * 2.5), 2);
+    assert_eq!(sim.bodies[0].position, Point { x: 2.0, y: 0.0 });
+    assert_eq!(center(clock.shape(&sim, 0)), Point { x: 1.5, y: 0.0 });
+}
@@ -0,0 +1,102 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::broadphase::*;
+use stad_demos::fixed::*;
+use stad_demos::tolerance::*;
+
+fn circle(x: f64, y: f64, r: f64) -> Shape {
+    Shape::Circle(Circle { p: Point { x, y }, r })
+}
+
+fn stad(x1: f64, y1: f64, x2: f64, y2: f64, r: f64) -> Shape {
+    Shape::Stad(Stad::new(x1, y1, x2, y2, r))
+}
+
+const RIGHT: Vector = Vector { x: 1.0, y: 0.0 };
+const DOWN: Vector = Vector { x: 0.0, y: 1.0 };
+
+// pairs whose surfaces meet exactly, with no rounding in the gap, and the
+// way from the first to the second
+fn touching() -> Vec<(Shape, Shape, Vector)> {
+    vec![
+	(circle(0.0, 0.0, 10.0), circle(30.0, 0.0, 20.0), RIGHT),
+	(circle(0.0, 0.0, 10.0), stad(-50.0, 15.0, 50.0, 15.0, 5.0), DOWN),
+	(stad(0.0, 0.0, 100.0, 0.0, 10.0), stad(0.0, 30.0, 100.0, 30.0, 20.0), DOWN),
+	(stad(0.0, 0.0, 100.0, 0.0, 10.0), stad(120.0, -50.0, 120.0, 50.0, 10.0), RIGHT),
+    ]
+}
+
+#[test]
+fn touching_collides_and_has_a_contact() {
+    for &(a, b, _) in touching().iter() {
+	assert_eq!(a.gap(b), 0.0);
+	assert!(a.collides(b) && b.collides(a), "{:?} {:?}", a, b);
+	let c = a.contact(b).unwrap();
+	assert_eq!(c.depth, 0.0);
+	assert!(b.contact(a).is_some());
+	assert!(a.manifold(b).is_some());
+	assert_eq!(a.proximity(b, Tolerance::default()), Proximity::Touching);
+    }
+}
+
+#[test]
+fn just_outside_the_tolerance_is_apart() {
+    for &(a, b, away) in touching().iter() {
+	let near = b.translated(away.scaled(DEFAULT_EPSILON / 2.0));
+	assert!(a.collides(near) && a.contact(near).is_some(), "{:?} {:?}", a, near);
+	let apart = b.translated(away.scaled(DEFAULT_EPSILON * 2.0));
+	assert!(!a.collides(apart) && a.contact(apart).is_none(), "{:?} {:?}", a, apart);
+    }
+}
+
+#[test]
+fn points_on_the_boundary_are_contained() {
+    let c = circle(0.0, 0.0, 10.0);
+    let s = stad(0.0, 0.0, 100.0, 0.0, 10.0);
+    assert!(c.contains_point(Point { x: 10.0, y: 0.0 }));
+    assert!(s.contains_point(Point { x: 50.0, y: -10.0 }));
+    assert!(s.contains_point(Point { x: 110.0, y: 0.0 }));
+    assert!(!c.contains_point(Point { x: 10.001, y: 0.0 }));
+    assert!(!s.contains_point(Point { x: 50.0, y: -10.001 }));
+}
+
+#[test]
+fn a_looser_tolerance_counts_nearer_misses() {
+    let loose = Tolerance::new(0.5);
+    let a = circle(0.0, 0.0, 10.0);
+    let b = circle(30.25, 0.0, 20.0);
+    let s = stad(-50.0, 10.25, 50.0, 10.25, 0.0);
+    assert!(!a.collides(b) && a.collides_with(b, loose));
+    assert!(a.contact(b).is_none());
+    assert_eq!(a.contact_with(b, loose).unwrap().depth, 0.0);
+    assert!(!a.collides(s) && a.collides_with(s, loose));
+    assert!(!a.contains_point(Point { x: 10.25, y: 0.0 }));
+    assert!(a.contains_point_with(Point { x: 10.25, y: 0.0 }, loose));
+    assert!(!a.collides_with(circle(30.75, 0.0, 20.0), loose));
+}
+
+#[test]
+fn broadphase_pads_by_the_tolerance_given() {
+    let loose = Tolerance::new(0.5);
+    let colliders = vec![
+	Collider::new(circle(0.0, 0.0, 10.0), CollisionFilter::default(), 0),
+	Collider::new(circle(30.25, 0.0, 20.0), CollisionFilter::default(), 0),
+	Collider::new(circle(100.0, 0.0, 10.0), CollisionFilter::default(), 0),
+    ];
+    assert!(colliding_pairs(&colliders).is_empty());
+    assert_eq!(candidate_pairs_with(&colliders, loose), vec![(0, 1)]);
+    assert_eq!(colliding_pairs_with(&colliders, loose), vec![(0, 1)]);
+    assert!(overlapping(&colliders, &colliders[0]).iter().all(|&i| i == 0));
+    assert_eq!(overlapping_with(&colliders, &colliders[0], loose), vec![0, 1]);
+    let b = colliders[1].shape.aabb_with(loose);
+    assert_eq!(b.min.x, 30.25 - 20.5);
+}
+
+#[test]
+fn fixed_point_touching_collides_too() {
+    for &(a, b, _) in touching().iter() {
+	let (fa, fb) = (FixedShape::from_shape(a), FixedShape::from_shape(b));
+	assert!(fa.collides(fb));
+	assert_eq!(fa.contact(fb).unwrap().depth, Fixed::ZERO);
+    }
+}
@@ -0,0 +1,125 @@
+use stad_demos::geometry::*;
+use stad_demos::collider::*;
+use stad_demos::query::Ray;
+use stad_demos::world::*;
+
+fn circle(x: f64, y: f64, r: f64) -> Circle {
+    Circle { p: Point { x, y }, r }
+}
+
+#[test]
+fn stale_handle_rejected_after_slot_reuse() {
+    let mut world = World::new();
+    let old = world.add_circle(circle(0.0, 0.0, 1.0));
+    let other = world.add_circle(circle(50.0, 0.0, 1.0));
+    assert!(world.remove(old).is_some());
+
+    // the freed slot is reused, under a new generation
+    let new = world.add_circle(circle(10.0, 0.0, 2.0));
+    assert_ne!(new, old);
+    assert_eq!(world.len(), 2);
+
+    assert!(!world.contains(old));
+    assert!(world.get(old).is_none());
+    assert!(world.get_mut(old).is_none());
+    assert!(!world.set_shape(old, Shape::Circle(circle(0.0, 0.0, 5.0))));
+    assert!(!world.translate(old, Vector { x: 1.0, y: 0.0 }));
+    assert!(world.remove(old).is_none());
+
+    // and none of that touched what's in the slot now
+    match world.get(new).unwrap().shape {
+	Shape::Circle(c) => assert_eq!((c.p, c.r), (Point { x: 10.0, y: 0.0 }, 2.0)),
+	shape => panic!("expected the new circle, got {:?}", shape),
+    }
+    assert!(world.contains(other));
+}
+
+#[test]
+fn removing_twice_does_not_free_the_slot_twice() {
+    let mut world = World::new();
+    let a = world.add_circle(circle(0.0, 0.0, 1.0));
+    world.remove(a);
+    world.remove(a);
+    let b = world.add_circle(circle(0.0, 0.0, 1.0));
+    let c = world.add_circle(circle(0.0, 0.0, 1.0));
+    assert_ne!(b, c);
+    assert_eq!(world.len(), 2);
+}
+
+#[test]
+fn queries_check_the_filter_both_ways() {
+    let mut world = World::new();
+    // on layer 2, but only willing to touch layer 4
+    let picky = world.add(Collider::new(Shape::Circle(circle(0.0, 0.0, 5.0)),
+					CollisionFilter::new(2, 4), 0));
+    let p = Point { x: 1.0, y: 1.0 };
+    // the query's mask takes layer 2, but its group isn't in picky's mask
+    assert!(world.query_point(p, CollisionFilter::new(1, 2)).is_empty());
+    assert_eq!(world.query_point(p, CollisionFilter::new(4, 2)), vec![picky]);
+}
+
+#[test]
+fn colliding_pairs_include_sensors() {
+    let mut world = World::new();
+    let solid = world.add_circle(circle(0.0, 0.0, 1.0));
+    let sensor = world.add(Collider::sensor(Shape::Circle(circle(1.0, 0.0, 1.0)),
+					    CollisionFilter::default(), 0));
+    assert_eq!(world.colliding_pairs(), vec![(solid, sensor)]);
+}
+
+fn ray(x: f64, y: f64, dx: f64, dy: f64) -> Ray {
+    Ray { origin: Point { x, y }, dir: Vector { x: dx, y: dy } }
+}
+
+#[test]
+fn raycast_finds_the_nearest_of_several() {
+    let mut world = World::new();
+    // added far to near, so slot order isn't what picks the answer
+    let far = world.add_circle(circle(100.0, 0.0, 5.0));
+    let near = world.add_stad(Stad::new(40.0, -20.0, 40.0, 20.0, 2.0));
+    let middle = world.add_circle(circle(70.0, 0.0, 5.0));
+    let _off_the_ray = world.add_circle(circle(20.0, 30.0, 5.0));
+    let all = CollisionFilter::default();
+
+    let hit = world.raycast(ray(0.0, 0.0, 1.0, 0.0), 1000.0, all).unwrap();
+    assert_eq!(hit.handle, near);
+    assert!((hit.t - 38.0).abs() < 1e-9);
+    assert!(hit.point.distance(Point { x: 38.0, y: 0.0 }) < 1e-9);
+
+    // with the nearest gone the next one along is hit
+    world.remove(near);
+    assert_eq!(world.raycast(ray(0.0, 0.0, 1.0, 0.0), 1000.0, all).unwrap().handle, middle);
+    // and nothing past max_t is
+    assert!(world.raycast(ray(0.0, 0.0, 1.0, 0.0), 60.0, all).is_none());
+    // from the other side the order flips
+    assert_eq!(world.raycast(ray(200.0, 0.0, -1.0, 0.0), 1000.0, all).unwrap().handle, far);
+}
+
+#[test]
+fn raycast_from_inside_hits_at_zero() {
+    let mut world = World::new();
+    let _ahead = world.add_circle(circle(50.0, 0.0, 5.0));
+    let around = world.add_stad(Stad::new(-10.0, 0.0, 10.0, 0.0, 5.0));
+    let hit = world.raycast(ray(0.0, 1.0, 1.0, 0.0), 1000.0, CollisionFilter::default()).unwrap();
+    assert_eq!(hit.handle, around);
+    assert_eq!(hit.t, 0.0);
+    assert_eq!(hit.point, Point { x: 0.0, y: 1.0 });
+}
+
+#[test]
+fn raycast_skips_removed_and_filtered_colliders() {
+    let mut world = World::new();
+    let removed = world.add_circle(circle(20.0, 0.0, 5.0));
+    let hidden = world.add(Collider::new(Shape::Circle(circle(40.0, 0.0, 5.0)),
+					 CollisionFilter::new(2, 2), 0));
+    let visible = world.add_circle(circle(60.0, 0.0, 5.0));
+    world.remove(removed);
+    // a new collider in the freed slot, off the ray
+    let reused = world.add_circle(circle(20.0, 50.0, 5.0));
+    assert_ne!(reused, removed);
+
+    let hit = world.raycast(ray(0.0, 0.0, 1.0, 0.0), 1000.0, CollisionFilter::default()).unwrap();
+    assert_eq!(hit.handle, visible);
+    assert!((hit.t - 55.0).abs() < 1e-9);
+    assert_eq!(world.raycast(ray(0.0, 0.0, 1.0, 0.0), 1000.0, CollisionFilter::new(2, 2)).unwrap().handle, hidden);
+}